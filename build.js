@@ -5,6 +5,12 @@ let release = ''
 if(args[args.length - 1] === '--release') {
     release = '--release'
 }
+// Cargo features of the native module, e.g. `--features smartengine`
+let features = ''
+const featuresIndex = args.indexOf('--features')
+if(featuresIndex !== -1 && args[featuresIndex + 1]) {
+    features = `-- --features ${args[featuresIndex + 1]}`
+}
 
 const asyncExec = async (cmd) => {
     const events = exec(cmd, (error, stdout, stderr) => {
@@ -41,5 +47,5 @@ function getDistPath() {
 
 // Dynamically build native package;
 (async () => {
-  return await asyncExec(`nj-cli build -o ${getDistPath()} ${release}`);
+  return await asyncExec(`nj-cli build -o ${getDistPath()} ${release} ${features}`);
 })()
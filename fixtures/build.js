@@ -0,0 +1,26 @@
+const execSync = require('child_process').execSync
+const fs = require('fs')
+const path = require('path')
+
+// SmartModule crates built for the tests, next to the `.wasm` they produce
+const fixtures = ['max-value-filter', 'level-filter']
+
+// SmartModules import their host functions, which the linker can't resolve
+const env = {
+    ...process.env,
+    RUSTFLAGS: '-C link-arg=--allow-undefined',
+}
+
+for (const fixture of fixtures) {
+    const dir = path.join(__dirname, fixture)
+    const name = fixture.replace(/-/g, '_')
+    execSync('cargo build --target wasm32-unknown-unknown --profile release-lto', {
+        cwd: dir,
+        env,
+        stdio: 'inherit',
+    })
+    fs.copyFileSync(
+        path.join(dir, 'target/wasm32-unknown-unknown/release-lto', `${name}.wasm`),
+        path.join(__dirname, `${name}.wasm`)
+    )
+}
@@ -0,0 +1,29 @@
+use once_cell::sync::OnceCell;
+use serde::Deserialize;
+
+use fluvio_smartmodule::dataplane::smartmodule::{SmartModuleExtraParams, SmartModuleInitError};
+use fluvio_smartmodule::{eyre, smartmodule, Result, SmartModuleRecord};
+
+/// Level of the records to drop, read from the `level` param
+static LEVEL: OnceCell<String> = OnceCell::new();
+
+#[derive(Deserialize)]
+struct LogRecord {
+    level: String,
+}
+
+#[smartmodule(init)]
+fn init(params: SmartModuleExtraParams) -> Result<()> {
+    let level = params
+        .get("level")
+        .ok_or_else(|| SmartModuleInitError::MissingParam("level".to_string()))?;
+    LEVEL
+        .set(level.clone())
+        .map_err(|err| eyre!("failed setting level: {:#?}", err))
+}
+
+#[smartmodule(filter)]
+pub fn filter(record: &SmartModuleRecord) -> Result<bool> {
+    let log: LogRecord = serde_json::from_slice(record.value.as_ref())?;
+    Ok(LEVEL.get() != Some(&log.level))
+}
@@ -0,0 +1,22 @@
+use std::sync::atomic::{AtomicI64, Ordering};
+
+use fluvio_smartmodule::{smartmodule, Result, SmartModuleRecord};
+
+/// Largest number seen so far, including records looked back at
+static MAX: AtomicI64 = AtomicI64::new(i64::MIN);
+
+#[smartmodule(look_back)]
+pub fn look_back(record: &SmartModuleRecord) -> Result<()> {
+    MAX.fetch_max(number(record)?, Ordering::Relaxed);
+    Ok(())
+}
+
+#[smartmodule(filter)]
+pub fn filter(record: &SmartModuleRecord) -> Result<bool> {
+    let number = number(record)?;
+    Ok(MAX.fetch_max(number, Ordering::Relaxed) < number)
+}
+
+fn number(record: &SmartModuleRecord) -> Result<i64> {
+    Ok(std::str::from_utf8(record.value.as_ref())?.trim().parse()?)
+}
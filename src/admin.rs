@@ -1,6 +1,8 @@
 use crate::{CLIENT_NOT_FOUND_ERROR_MSG};
 use crate::{optional_property, must_property};
 use crate::error::FluvioErrorJS;
+use crate::producer::ProduceArg;
+use crate::smartmodule::{read_wasm_data, read_wasm_file};
 
 use std::convert::TryInto;
 use std::fmt::Debug;
@@ -16,6 +18,9 @@ use fluvio::metadata::topic::{PartitionMap};
 use fluvio::metadata::objects::Metadata;
 use fluvio::metadata::partition::{PartitionSpec, PartitionStatus, PartitionResolution, ReplicaStatus};
 use fluvio::metadata::topic::TopicSpec;
+use fluvio::metadata::smartmodule::{
+    SmartModuleMetadata, SmartModuleSpec, SmartModuleWasm, SmartModuleWasmFormat,
+};
 
 use serde::Serialize;
 use node_bindgen::derive::node_bindgen;
@@ -40,6 +45,9 @@ const LEADER_KEY: &str = "leader";
 const RESOLUTION_KEY: &str = "resolution";
 const LSR_KEY: &str = "lsr";
 const REPLICAS_KEY: &str = "replicas";
+const FILE_KEY: &str = "file";
+const DATA_KEY: &str = "data";
+const METADATA_KEY: &str = "metadata";
 
 impl From<FluvioAdmin> for FluvioAdminJS {
     fn from(inner: FluvioAdmin) -> Self {
@@ -98,7 +106,7 @@ impl FluvioAdminJS {
     async fn list_topic(&mut self) -> Result<ArrayBuffer, FluvioErrorJS> {
         self.js_list::<TopicSpec>()
             .await
-            .map_err(|err| FluvioErrorJS::new(err.to_string()))
+            .map_err(FluvioErrorJS::from)
     }
 
     #[node_bindgen]
@@ -144,9 +152,7 @@ impl FluvioAdminJS {
 
     #[node_bindgen]
     async fn list_spu(&mut self) -> Result<ArrayBuffer, FluvioErrorJS> {
-        self.js_list::<SpuSpec>()
-            .await
-            .map_err(|err| FluvioErrorJS::new(err.to_string()))
+        self.js_list::<SpuSpec>().await.map_err(FluvioErrorJS::from)
     }
 
     #[node_bindgen]
@@ -180,7 +186,7 @@ impl FluvioAdminJS {
     async fn list_partitions(&mut self) -> Result<ArrayBuffer, FluvioErrorJS> {
         self.js_list::<PartitionSpec>()
             .await
-            .map_err(|err| FluvioErrorJS::new(err.to_string()))
+            .map_err(FluvioErrorJS::from)
     }
 
     #[node_bindgen]
@@ -207,6 +213,133 @@ impl FluvioAdminJS {
             Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
         }
     }
+
+    /// Registers a SmartModule, so consumers and producers can refer to it by name
+    #[node_bindgen]
+    async fn create_smart_module(
+        &mut self,
+        name: String,
+        spec: SmartModuleSpecWrapper,
+    ) -> Result<String, FluvioErrorJS> {
+        if let Some(client) = &mut self.inner {
+            debug!("Creating SmartModule {}", name);
+            client.create(name.clone(), false, spec.0).await?;
+            Ok(name)
+        } else {
+            Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
+        }
+    }
+
+    /// Lists the SmartModules matching `filters`, all of them if empty.
+    /// Only a summary is requested, so WASM payloads are not transferred.
+    async fn smart_modules(&mut self, filters: Vec<String>) -> Result<Vec<SmartModuleInfo>> {
+        let client = self.client()?;
+        let smartmodules = client
+            .list_with_params::<SmartModuleSpec, _>(filters, true)
+            .await?;
+        Ok(smartmodules
+            .into_iter()
+            .map(SmartModuleInfo::from)
+            .collect())
+    }
+
+    #[node_bindgen]
+    async fn list_smart_modules(&mut self) -> Result<ArrayBuffer, FluvioErrorJS> {
+        let smartmodules = self.smart_modules(vec![]).await?;
+        let json = serde_json::to_vec(&smartmodules).map_err(anyhow::Error::from)?;
+        Ok(ArrayBuffer::new(json))
+    }
+
+    /// Resolves with `null` if no SmartModule is registered as `name`
+    #[node_bindgen]
+    async fn find_smart_module(&mut self, name: String) -> Result<ArrayBuffer, FluvioErrorJS> {
+        let smartmodule = self
+            .smart_modules(vec![name.clone()])
+            .await?
+            .into_iter()
+            .find(|sm| sm.name == name);
+        let json = serde_json::to_vec(&smartmodule).map_err(anyhow::Error::from)?;
+        Ok(ArrayBuffer::new(json))
+    }
+
+    #[node_bindgen]
+    async fn delete_smart_module(&mut self, name: String) -> Result<String, FluvioErrorJS> {
+        if let Some(client) = &mut self.inner {
+            client.delete::<SmartModuleSpec>(name.clone()).await?;
+            Ok(name)
+        } else {
+            Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
+        }
+    }
+}
+
+/// SmartModule to register, read from one of `file` or `data`,
+/// and described by the `SmartModule.toml` at `metadata`, if given
+pub struct SmartModuleSpecWrapper(SmartModuleSpec);
+
+impl JSValue<'_> for SmartModuleSpecWrapper {
+    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        if let Ok(js_obj) = env.convert_to_rust::<JsObject>(js_value) {
+            let file = optional_property!(FILE_KEY, String, js_obj);
+            let data = optional_property!(DATA_KEY, ProduceArg, js_obj);
+            let metadata = optional_property!(METADATA_KEY, String, js_obj);
+
+            let payload = match (file, data) {
+                (Some(file_path), None) => read_wasm_file(&file_path)?,
+                (None, Some(data)) => read_wasm_data(data)?,
+                _ => {
+                    return Err(NjError::Other(format!(
+                        "You must provide one of {} or {}",
+                        FILE_KEY, DATA_KEY
+                    )))
+                }
+            };
+
+            let meta = match metadata {
+                Some(path) => Some(SmartModuleMetadata::from_toml(&path).map_err(|err| {
+                    NjError::Other(format!("Failed to read SmartModule metadata {path}: {err}"))
+                })?),
+                None => None,
+            };
+
+            Ok(Self(SmartModuleSpec {
+                meta,
+                wasm: SmartModuleWasm {
+                    format: SmartModuleWasmFormat::Binary,
+                    payload: payload.into(),
+                },
+                ..Default::default()
+            }))
+        } else {
+            Err(NjError::Other("must pass json param".to_owned()))
+        }
+    }
+}
+
+/// A registered SmartModule, without its WASM payload
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SmartModuleInfo {
+    name: String,
+    wasm_size: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    meta: Option<SmartModuleMetadata>,
+}
+
+impl From<Metadata<SmartModuleSpec>> for SmartModuleInfo {
+    fn from(metadata: Metadata<SmartModuleSpec>) -> Self {
+        let spec = metadata.spec;
+        // Summaries leave the payload out and report its size instead
+        let wasm_size = match spec.summary {
+            Some(summary) => summary.wasm_length as usize,
+            None => spec.wasm.payload.len(),
+        };
+        Self {
+            name: metadata.name,
+            wasm_size,
+            meta: spec.meta,
+        }
+    }
 }
 
 pub struct ReplicaStatusWrapper(ReplicaStatus);
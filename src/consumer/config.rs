@@ -1,16 +1,10 @@
-use std::io::prelude::*;
-use std::io::BufReader;
-use std::fs::File;
-use std::path::PathBuf;
-use std::str::FromStr;
+use std::time::Duration;
 
 use tracing::debug;
-use base64::Engine;
-use flate2::write::GzEncoder;
-use flate2::Compression;
 
 use fluvio::ConsumerConfig;
-use fluvio::{SmartModuleInvocation, SmartModuleKind, SmartModuleInvocationWasm};
+use fluvio::SmartModuleInvocation;
+use fluvio::consumer::{ConsumerConfigExtBuilder, OffsetManagementStrategy};
 
 use node_bindgen::core::NjError;
 use node_bindgen::core::JSValue;
@@ -18,123 +12,118 @@ use node_bindgen::core::val::JsEnv;
 use node_bindgen::sys::napi_value;
 use node_bindgen::core::val::JsObject;
 
-use crate::{optional_property, must_property};
+use crate::optional_property;
+use crate::smartmodule::smartmodule_invocations;
 
 const CONFIG_SMART_MODULE_MAX_BYTES_KEY: &str = "maxBytes";
-const CONFIG_SMART_MODULE_DATA_KEY: &str = "smartmoduleData";
-const CONFIG_SMART_MODULE_TYPE_KEY: &str = "smartmoduleType";
-const CONFIG_SMART_MODULE_NAME_KEY: &str = "smartmoduleName";
-const CONFIG_SMART_MODULE_FILE_KEY: &str = "smartmoduleFile";
+
+const CONFIG_CONSUMER_ID_KEY: &str = "consumerId";
+const CONFIG_OFFSET_STRATEGY_KEY: &str = "offsetStrategy";
+const CONFIG_OFFSET_FLUSH_KEY: &str = "offsetFlushMs";
 
 pub struct ConfigWrapper {
-    pub inner: ConsumerConfig,
+    pub max_bytes: Option<i32>,
+    pub smartmodule: Vec<SmartModuleInvocation>,
+}
+
+impl ConfigWrapper {
+    /// Builds the config used by the single partition consumer API
+    pub fn consumer_config(self) -> Result<ConsumerConfig, NjError> {
+        let mut config_builder = ConsumerConfig::builder();
+        config_builder.smartmodule(self.smartmodule);
+
+        if let Some(max_bytes) = self.max_bytes {
+            config_builder.max_bytes(max_bytes);
+        };
+
+        config_builder
+            .build()
+            .map_err(|e| NjError::Other(e.to_string()))
+    }
+
+    /// Applies these options to a topic wide consumer config
+    pub fn apply(self, builder: &mut ConsumerConfigExtBuilder) {
+        builder.smartmodule(self.smartmodule);
+
+        if let Some(max_bytes) = self.max_bytes {
+            builder.max_bytes(max_bytes);
+        };
+    }
 }
 
 impl JSValue<'_> for ConfigWrapper {
     fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
         debug!("convert fetch consumer config param");
         if let Ok(js_obj) = env.convert_to_rust::<JsObject>(js_value) {
-            let smartmodule_type = must_property!(CONFIG_SMART_MODULE_TYPE_KEY, String, js_obj);
-            let smartmodule_file = optional_property!(CONFIG_SMART_MODULE_FILE_KEY, String, js_obj);
-            let smartmodule_name = optional_property!(CONFIG_SMART_MODULE_NAME_KEY, String, js_obj);
-            let smartmodule_data = optional_property!(CONFIG_SMART_MODULE_DATA_KEY, String, js_obj);
-            let kind = match smartmodule_type.as_str() {
-                "filter" => Ok(SmartModuleKind::Filter),
-                "map" => Ok(SmartModuleKind::Map),
-                "array_map" => Ok(SmartModuleKind::ArrayMap),
-                "filter_map" => Ok(SmartModuleKind::FilterMap),
-                _ => Err(NjError::Other(format!(
-                    "Provided SmartModule type: \"{}\" is not valid",
-                    smartmodule_type
-                ))),
-            }?;
-
-            let mut config_builder = ConsumerConfig::builder();
-            let smartmodule: Vec<SmartModuleInvocation> =
-                match (smartmodule_file, smartmodule_name, smartmodule_data) {
-                    (None, None, None) => Ok(vec![]),
-                    (Some(file_path), None, None) => {
-                        debug!("Loads SmartModule file from {}", file_path);
-                        let path = PathBuf::from_str(file_path.as_str())
-                            .map_err(|e| NjError::Other(e.to_string()))?;
-                        let file = File::open(path).map_err(|io_err| {
-                            NjError::Other(format!(
-                                "An error ocurred opening file on {}. {:?}",
-                                file_path, io_err,
-                            ))
-                        })?;
-                        let mut reader = BufReader::new(file);
-                        let mut gz_encoder = GzEncoder::new(Vec::new(), Compression::default());
-                        let mut buff: Vec<u8> = Vec::new();
-
-                        reader.read_to_end(&mut buff).map_err(|io_err| {
-                            NjError::Other(format!(
-                                "Failed to read contents of file on {}: {:?}",
-                                file_path, io_err
-                            ))
-                        })?;
-                        gz_encoder.write_all(buff.as_slice()).map_err(|io_err| {
-                            NjError::Other(format!(
-                                "An error ocurred while reading WASM file on {}: {:?}",
-                                file_path, io_err
-                            ))
-                        })?;
-                        let bytes = gz_encoder.finish().map_err(|io_err| {
-                            NjError::Other(format!(
-                                "An error ocurred while encoding WASM into Gzip. {:?}",
-                                io_err
-                            ))
-                        })?;
-
-                        Ok(vec![SmartModuleInvocation {
-                            wasm: SmartModuleInvocationWasm::AdHoc(bytes),
-                            kind,
-                            ..Default::default()
-                        }])
-                    }
-                    (None, Some(name), None) => Ok(vec![SmartModuleInvocation {
-                        wasm: SmartModuleInvocationWasm::Predefined(name),
-                        kind,
-                        ..Default::default()
-                    }]),
-                    (None, None, Some(data)) => {
-                        let wasm = base64::engine::general_purpose::STANDARD
-                            .decode(data)
-                            .map_err(|e| {
-                                NjError::Other(format!(
-                "An error ocurred attempting to decode the Base64 WASM file provided. {:?}",
-                e
-            ))
-                            })?;
-
-                        Ok(vec![SmartModuleInvocation {
-                            wasm: SmartModuleInvocationWasm::AdHoc(wasm),
-                            kind,
-                            ..Default::default()
-                        }])
-                    }
-                    _ => Err(NjError::Other(format!(
-                        "You must either provide one of {}, {} or {}",
-                        CONFIG_SMART_MODULE_FILE_KEY,
-                        CONFIG_SMART_MODULE_NAME_KEY,
-                        CONFIG_SMART_MODULE_DATA_KEY
-                    ))),
-                }?;
+            let smartmodule = smartmodule_invocations(&js_obj)?;
+            let max_bytes = optional_property!(CONFIG_SMART_MODULE_MAX_BYTES_KEY, i32, js_obj);
+
+            return Ok(Self {
+                max_bytes,
+                smartmodule,
+            });
+        }
+
+        Err(NjError::Other("must pass json param".to_owned()))
+    }
+}
+
+/// Stored offset settings for a `TopicConsumerJS`
+#[derive(Clone, Default)]
+pub struct OffsetConfigWrapper {
+    consumer_id: Option<String>,
+    strategy: Option<OffsetManagementStrategy>,
+    flush: Option<Duration>,
+}
 
-            config_builder.smartmodule(smartmodule);
+impl OffsetConfigWrapper {
+    pub fn apply(&self, builder: &mut ConsumerConfigExtBuilder) {
+        if let Some(consumer_id) = &self.consumer_id {
+            builder.offset_consumer(consumer_id.clone());
+            // Offsets are committed automatically unless asked otherwise
+            builder.offset_strategy(self.strategy.unwrap_or(OffsetManagementStrategy::Auto));
+        } else if let Some(strategy) = self.strategy {
+            builder.offset_strategy(strategy);
+        }
 
-            if let Some(max_bytes) =
-                optional_property!(CONFIG_SMART_MODULE_MAX_BYTES_KEY, i32, js_obj)
-            {
-                config_builder.max_bytes(max_bytes);
-            };
+        if let Some(flush) = self.flush {
+            builder.offset_flush(flush);
+        }
+    }
+}
 
-            let consumer_config = config_builder
-                .build()
-                .map_err(|e| NjError::Other(e.to_string()))?;
+impl JSValue<'_> for OffsetConfigWrapper {
+    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        debug!("convert consumer offset config param");
+        if let Ok(js_obj) = env.convert_to_rust::<JsObject>(js_value) {
+            let consumer_id = optional_property!(CONFIG_CONSUMER_ID_KEY, String, js_obj);
+            let strategy = optional_property!(CONFIG_OFFSET_STRATEGY_KEY, String, js_obj)
+                .map(|strategy| match strategy.as_str() {
+                    "auto" => Ok(OffsetManagementStrategy::Auto),
+                    "manual" => Ok(OffsetManagementStrategy::Manual),
+                    "none" => Ok(OffsetManagementStrategy::None),
+                    _ => Err(NjError::Other(format!(
+                        "Provided offset strategy: \"{}\" is not valid",
+                        strategy
+                    ))),
+                })
+                .transpose()?;
+            let flush = optional_property!(CONFIG_OFFSET_FLUSH_KEY, f64, js_obj)
+                .map(|flush_ms| {
+                    if !flush_ms.is_finite() || flush_ms < 0.0 {
+                        return Err(NjError::Other(format!(
+                            "{} must be a non-negative number of milliseconds",
+                            CONFIG_OFFSET_FLUSH_KEY
+                        )));
+                    }
+                    Ok(Duration::from_millis(flush_ms as u64))
+                })
+                .transpose()?;
 
             return Ok(Self {
-                inner: consumer_config,
+                consumer_id,
+                strategy,
+                flush,
             });
         }
 
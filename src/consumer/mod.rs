@@ -1,23 +1,40 @@
 mod config;
+mod task;
+mod topic;
 
-use crate::{OFFSET_BEGINNING, OFFSET_END, CLIENT_NOT_FOUND_ERROR_MSG};
+pub use self::config::{ConfigWrapper, OffsetConfigWrapper};
+pub use self::topic::{TopicConsumer, TopicConsumerJS};
+
+use crate::{OFFSET_BEGINNING, OFFSET_END, OFFSET_ABSOLUTE, CLIENT_NOT_FOUND_ERROR_MSG};
 use crate::{optional_property, must_property};
 use crate::error::FluvioErrorJS;
 
+use self::task::{
+    OffsetHandle, OffsetReply, OffsetRequest, OffsetRequestKind, Signal, StreamTask, next_until,
+};
+
 use std::fmt;
-use std::pin::Pin;
+use std::future::{Future, pending, poll_fn};
+use std::pin::{Pin, pin};
 use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::Duration;
 
 use tracing::{debug, error};
 use anyhow::Result;
 
-use fluvio::{PartitionConsumer, ConsumerConfig};
+use fluvio::{Fluvio, FluvioAdmin, FluvioError, PartitionConsumer, ConsumerConfig};
 use fluvio::{Offset};
-use fluvio::dataplane::record::RecordSet;
+use fluvio::dataplane::record::{RecordSet, ReplicaKey};
+use fluvio::metadata::partition::PartitionSpec;
 use fluvio::consumer::Record;
-use fluvio_future::task::spawn;
 use fluvio_future::io::{Stream, StreamExt};
+use fluvio_future::timer::sleep;
 use fluvio_spu_schema::fetch::{FetchablePartitionResponse, AbortedTransaction};
+use futures_channel::mpsc::UnboundedReceiver;
+use futures_util::FutureExt;
+use futures_util::future::{BoxFuture, Either, select};
+use futures_util::lock::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
 
 use node_bindgen::derive::node_bindgen;
 use node_bindgen::core::NjError;
@@ -28,6 +45,7 @@ use node_bindgen::sys::napi_value;
 use node_bindgen::core::JSClass;
 use node_bindgen::core::val::JsObject;
 use node_bindgen::core::buffer::ArrayBuffer;
+use node_bindgen::core::bigint::BigInt;
 
 const PRODUCER_ID_KEY: &str = "producerId";
 
@@ -55,13 +73,40 @@ const VALUE_KEY: &str = "value";
 
 const BATCHES_KEY: &str = "batches";
 
+const FETCH_MAX_BYTES_KEY: &str = "maxBytes";
+const FETCH_MAX_RECORDS_KEY: &str = "maxRecords";
+
+const RECORD_NOT_FOUND_ERROR_MSG: &str = "record not found; records are only created by consumers";
+
+const ITERATOR_CLOSED_MSG: &str = "the stream was closed";
+
+const OFFSET_MANAGEMENT_UNSUPPORTED_MSG: &str =
+    "offsets can only be managed on streams created by a TopicConsumer with a consumerId";
+
+/// Largest integer a JS number represents exactly (2^53 - 1)
+const MAX_SAFE_INTEGER: f64 = 9_007_199_254_740_991.0;
+
+const EVENT_TYPE_KEY: &str = "type";
+const EVENT_RECORD_KEY: &str = "record";
+const EVENT_ERROR_KEY: &str = "error";
+const EVENT_RECORD: &str = "record";
+const EVENT_ERROR: &str = "error";
+const EVENT_END: &str = "end";
+
+const ITER_VALUE_KEY: &str = "value";
+const ITER_DONE_KEY: &str = "done";
+
 impl TryIntoJs for PartitionConsumerJS {
     fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
         debug!("converting PartitionConsumerJS to js");
         let new_instance = PartitionConsumerJS::new_instance(js_env, vec![])?;
         debug!("instance created");
+        let consumer = PartitionConsumerJS::unwrap_mut(js_env, new_instance)?;
         if let Some(inner) = self.inner {
-            PartitionConsumerJS::unwrap_mut(js_env, new_instance)?.set_client(inner);
+            consumer.set_client(inner);
+        }
+        if let Some(fluvio) = self.fluvio {
+            consumer.set_fluvio(fluvio);
         }
         Ok(new_instance)
     }
@@ -69,30 +114,78 @@ impl TryIntoJs for PartitionConsumerJS {
 
 pub struct PartitionConsumerJS {
     inner: Option<Arc<PartitionConsumer>>,
+    // Used for the partition metadata reported by `fetch`
+    fluvio: Option<Arc<Fluvio>>,
+    // Opened by the first `fetch` and reused by the following ones
+    admin: AsyncMutex<Option<Arc<FluvioAdmin>>>,
+    tasks: Vec<StreamTask>,
 }
 
-impl From<PartitionConsumer> for PartitionConsumerJS {
-    fn from(inner: PartitionConsumer) -> Self {
+impl PartitionConsumerJS {
+    pub fn with_fluvio(inner: PartitionConsumer, fluvio: Arc<Fluvio>) -> Self {
         Self {
             inner: Some(Arc::new(inner)),
+            fluvio: Some(fluvio),
+            admin: AsyncMutex::new(None),
+            tasks: Vec::new(),
         }
     }
+
+    /// Admin client of the cluster, connected on first use
+    async fn admin(&self) -> Result<Arc<FluvioAdmin>, FluvioErrorJS> {
+        let fluvio = self
+            .fluvio
+            .as_ref()
+            .ok_or_else(|| FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_string()))?;
+        let mut cached = self.admin.lock().await;
+        if let Some(admin) = cached.as_ref() {
+            return Ok(admin.clone());
+        }
+        let admin = Arc::new(fluvio.admin().await);
+        cached.replace(admin.clone());
+        Ok(admin)
+    }
+
+    /// High watermark of the partition, as reported by its leader
+    async fn high_watermark(&self, topic: &str, partition: u32) -> Result<i64, FluvioErrorJS> {
+        let name = ReplicaKey::new(topic, partition).to_string();
+        let admin = self.admin().await?;
+        let metadata = admin
+            .list::<PartitionSpec, _>(vec![name.clone()])
+            .await?
+            .into_iter()
+            .find(|metadata| metadata.name == name)
+            .ok_or_else(|| FluvioError::PartitionNotFound(topic.to_owned(), partition))?;
+        Ok(metadata.status.leader.hw)
+    }
 }
 
 #[node_bindgen]
 impl PartitionConsumerJS {
     #[node_bindgen(constructor)]
     pub fn new() -> Self {
-        Self { inner: None }
+        Self {
+            inner: None,
+            fluvio: None,
+            admin: AsyncMutex::new(None),
+            tasks: Vec::new(),
+        }
     }
 
     pub fn set_client(&mut self, client: Arc<PartitionConsumer>) {
         self.inner.replace(client);
     }
 
+    pub fn set_fluvio(&mut self, fluvio: Arc<Fluvio>) {
+        self.fluvio.replace(fluvio);
+    }
+
+    /// Opens the stream before spawning it, so errors connecting to the
+    /// partition reject the returned promise. Errors on the running stream
+    /// are delivered to `cb` as `StreamEventJS::Error`.
     #[node_bindgen(mt)]
-    async fn stream<F: Fn(RecordJS) + 'static + Send + Sync>(
-        &self,
+    async fn stream<F: Fn(StreamEventJS) + 'static + Send + Sync>(
+        &mut self,
         offset: OffsetWrapper,
         cb: F,
     ) -> Result<(), FluvioErrorJS> {
@@ -100,27 +193,27 @@ impl PartitionConsumerJS {
             .inner
             .as_ref()
             .ok_or_else(|| FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_string()))?;
-        spawn(Self::stream_inner(client.clone(), offset, cb));
-        Ok(())
-    }
 
-    async fn stream_inner<F: Fn(RecordJS)>(
-        client: Arc<PartitionConsumer>,
-        offset: OffsetWrapper,
-        cb: F,
-    ) -> Result<()> {
+        let topic: Arc<str> = client.topic().into();
         #[allow(deprecated)]
-        let mut stream = client.stream(offset.0).await?;
+        let stream = client.stream(offset.0).await?;
 
-        debug!("Waiting for stream");
-        while let Some(next) = stream.next().await {
-            match next {
-                Ok(record) => cb(RecordJS::from(record)),
-                Err(e) => error!("Error consuming record: {:?}", e),
-            }
-        }
-        debug!("Stream ended!");
+        // Forget about streams that already ended on their own
+        self.tasks.retain(|task| !task.is_finished());
+        self.tasks.push(StreamTask::spawn(move |stop| {
+            stream_events(stream, topic, cb, stop)
+        }));
+        Ok(())
+    }
 
+    /// Stops every stream started with `stream`, resolving once their tasks have finished
+    #[node_bindgen]
+    async fn end_stream(&mut self) -> Result<(), FluvioErrorJS> {
+        let tasks = std::mem::take(&mut self.tasks);
+        debug!("Stopping {} streams", tasks.len());
+        for task in tasks {
+            task.stop().await;
+        }
         Ok(())
     }
 
@@ -140,7 +233,8 @@ impl PartitionConsumerJS {
             .await
             .map_err(|err| FluvioErrorJS::new(err.to_string()))?;
         let mut iterator = PartitionConsumerIterator::new();
-        iterator.set_inner(Box::pin(stream));
+        iterator.set_inner(IteratorStream::Partition(Box::pin(stream)));
+        iterator.set_topic(client.topic().into());
         Ok(iterator)
     }
 
@@ -148,9 +242,11 @@ impl PartitionConsumerJS {
     async fn stream_with_config(
         &self,
         offset: OffsetWrapper,
-        config: config::ConfigWrapper,
+        config: ConfigWrapper,
     ) -> Result<PartitionConsumerIterator, FluvioErrorJS> {
-        let config: ConsumerConfig = config.inner;
+        let config: ConsumerConfig = config
+            .consumer_config()
+            .map_err(|err| FluvioErrorJS::new(err.to_string()))?;
         let client = self
             .inner
             .as_ref()
@@ -162,28 +258,197 @@ impl PartitionConsumerJS {
             .map_err(|err| FluvioErrorJS::new(err.to_string()))?;
         let mut iterator = PartitionConsumerIterator::new();
 
-        iterator.set_inner(Box::pin(stream));
+        iterator.set_inner(IteratorStream::Partition(Box::pin(stream)));
+        iterator.set_topic(client.topic().into());
 
         Ok(iterator)
     }
+
+    #[node_bindgen]
+    async fn fetch(
+        &self,
+        offset: OffsetWrapper,
+        config: FetchConfigWrapper,
+    ) -> Result<FetchablePartitionResponseWrapper, FluvioErrorJS> {
+        let client = self
+            .inner
+            .as_ref()
+            .ok_or_else(|| FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_string()))?;
+
+        // Stop at the end of the partition instead of waiting for new records
+        let mut config_builder = ConsumerConfig::builder();
+        config_builder.disable_continuous(true);
+        if let Some(max_bytes) = config.max_bytes {
+            config_builder.max_bytes(max_bytes);
+        }
+        let consumer_config = config_builder
+            .build()
+            .map_err(|err| FluvioErrorJS::new(err.to_string()))?;
+
+        #[allow(deprecated)]
+        let mut stream = client
+            .stream_batches_with_config(offset.0, consumer_config)
+            .await?;
+
+        let max_records = config.max_records.unwrap_or(usize::MAX);
+        let mut batches = Vec::new();
+        let mut record_count = 0;
+        while record_count < max_records {
+            let Some(next) = stream.next().await else {
+                break;
+            };
+            let mut batch = next?;
+
+            let remaining = max_records - record_count;
+            if batch.records().len() > remaining {
+                batch.mut_records().truncate(remaining);
+                batch.header.last_offset_delta = remaining as i32 - 1;
+            }
+
+            record_count += batch.records().len();
+            batches.push(batch);
+        }
+        debug!("Fetched {} records", record_count);
+
+        let next_offset = batches
+            .last()
+            .map(|batch| batch.base_offset + batch.header.last_offset_delta as i64 + 1);
+        let high_watermark = self
+            .high_watermark(client.topic(), client.partition())
+            .await?;
+
+        Ok(FetchablePartitionResponseWrapper(
+            Some(FetchablePartitionResponse {
+                partition_index: client.partition(),
+                high_watermark,
+                records: RecordSet { batches },
+                ..Default::default()
+            }),
+            next_offset,
+        ))
+    }
+}
+
+/// Forwards `stream` to `cb` as `StreamEventJS`s until it ends or `stop` fires
+async fn stream_events<S, F>(mut stream: S, topic: Arc<str>, cb: F, stop: Arc<Signal>)
+where
+    S: Stream<Item = Result<Record, ErrorCode>> + Unpin,
+    F: Fn(StreamEventJS),
+{
+    debug!("Waiting for stream");
+    while let Some(next) = next_until(&mut stream, &stop).await {
+        emit_next(next, &topic, &cb);
+    }
+    // Close the stream on the SPU side before reporting the end
+    drop(stream);
+    debug!("Stream ended!");
+
+    cb(StreamEventJS::End);
+}
+
+/// Like `stream_events`, also serving the offset requests sent to the task
+async fn consumer_stream_events<S, F>(
+    mut stream: S,
+    topic: Arc<str>,
+    cb: F,
+    stop: Arc<Signal>,
+    mut requests: UnboundedReceiver<OffsetRequest>,
+) where
+    S: OffsetStream,
+    F: Fn(StreamEventJS),
+{
+    debug!("Waiting for stream");
+    loop {
+        let step = poll_fn(|cx| {
+            if stop.poll_notified(cx).is_ready() {
+                return Poll::Ready(None);
+            }
+            if let Poll::Ready(Some(request)) = Pin::new(&mut requests).poll_next(cx) {
+                return Poll::Ready(Some(StreamStep::Request(request)));
+            }
+            Pin::new(&mut stream)
+                .poll_next(cx)
+                .map(|next| Some(next.map_or(StreamStep::End, StreamStep::Next)))
+        })
+        .await;
+
+        match step {
+            Some(StreamStep::Next(next)) => emit_next(next, &topic, &cb),
+            // The requester may have gone away, so replies are allowed to fail
+            Some(StreamStep::Request(OffsetRequest::Commit(reply))) => {
+                let _ = reply.send(stream.offset_commit());
+            }
+            Some(StreamStep::Request(OffsetRequest::Flush(reply))) => {
+                let _ = reply.send(stream.offset_flush().await);
+            }
+            Some(StreamStep::End) | None => break,
+        }
+    }
+    // Close the stream on the SPU side before reporting the end
+    drop(stream);
+    debug!("Stream ended!");
+
+    cb(StreamEventJS::End);
+}
+
+enum StreamStep {
+    Next(Result<Record, ErrorCode>),
+    End,
+    Request(OffsetRequest),
+}
+
+fn emit_next<F>(next: Result<Record, ErrorCode>, topic: &Arc<str>, cb: &F)
+where
+    F: Fn(StreamEventJS),
+{
+    match next {
+        Ok(record) => cb(StreamEventJS::Record(
+            RecordJS::from(record).with_topic(Some(topic.clone())),
+        )),
+        Err(e) => {
+            error!("Error consuming record: {:?}", e);
+            cb(StreamEventJS::Error(e.into()));
+        }
+    }
 }
 
 #[derive(Clone)]
 pub struct RecordJS {
     inner: Option<Arc<Record>>,
+    topic: Option<Arc<str>>,
 }
 
 #[node_bindgen]
 impl RecordJS {
     #[node_bindgen(constructor)]
     pub fn new() -> Self {
-        Self { inner: None }
+        Self {
+            inner: None,
+            topic: None,
+        }
     }
 
     fn set_inner(&mut self, inner: Arc<Record>) {
         self.inner = Some(inner);
     }
 
+    fn set_topic(&mut self, topic: Option<Arc<str>>) {
+        self.topic = topic;
+    }
+
+    /// Tags this record with the topic it was consumed from
+    pub fn with_topic(mut self, topic: Option<Arc<str>>) -> Self {
+        self.topic = topic;
+        self
+    }
+
+    /// Records constructed from JS hold no consumed record
+    fn record(&self) -> Result<&Record, FluvioErrorJS> {
+        self.inner
+            .as_deref()
+            .ok_or_else(|| FluvioErrorJS::new(RECORD_NOT_FOUND_ERROR_MSG.to_string()))
+    }
+
     #[node_bindgen]
     pub fn key(&self) -> Option<ArrayBuffer> {
         let key = self.inner.as_ref()?.key()?;
@@ -191,13 +456,13 @@ impl RecordJS {
     }
 
     #[node_bindgen]
-    pub fn has_key(&self) -> bool {
-        self.inner.as_ref().unwrap().key().is_some()
+    pub fn has_key(&self) -> Result<bool, FluvioErrorJS> {
+        Ok(self.record()?.key().is_some())
     }
 
     #[node_bindgen]
-    pub fn value(&self) -> ArrayBuffer {
-        ArrayBuffer::new(self.inner.as_ref().unwrap().value().to_owned())
+    pub fn value(&self) -> Result<ArrayBuffer, FluvioErrorJS> {
+        Ok(ArrayBuffer::new(self.record()?.value().to_owned()))
     }
 
     #[node_bindgen]
@@ -207,9 +472,32 @@ impl RecordJS {
     }
 
     #[node_bindgen]
-    pub fn value_string(&self) -> String {
-        let value = self.inner.as_ref().unwrap().value();
-        String::from_utf8_lossy(value).to_string()
+    pub fn value_string(&self) -> Result<String, FluvioErrorJS> {
+        let value = self.record()?.value();
+        Ok(String::from_utf8_lossy(value).to_string())
+    }
+
+    /// Offsets are returned as BigInt so 64-bit values are not truncated
+    #[node_bindgen]
+    pub fn offset(&self) -> Result<BigInt, FluvioErrorJS> {
+        Ok(BigInt::from(self.record()?.offset()))
+    }
+
+    #[node_bindgen]
+    pub fn partition(&self) -> Result<u32, FluvioErrorJS> {
+        Ok(self.record()?.partition())
+    }
+
+    /// Milliseconds since the Unix epoch, as BigInt.
+    /// -1 when the record carries no timestamp.
+    #[node_bindgen]
+    pub fn timestamp(&self) -> Result<BigInt, FluvioErrorJS> {
+        Ok(BigInt::from(self.record()?.timestamp()))
+    }
+
+    #[node_bindgen]
+    pub fn topic(&self) -> Option<String> {
+        Some(self.topic.as_ref()?.to_string())
     }
 }
 
@@ -217,9 +505,11 @@ impl TryIntoJs for RecordJS {
     fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
         let new_instance = RecordJS::new_instance(js_env, vec![])?;
 
+        let record = RecordJS::unwrap_mut(js_env, new_instance)?;
         if let Some(inner) = self.inner {
-            RecordJS::unwrap_mut(js_env, new_instance)?.set_inner(inner);
+            record.set_inner(inner);
         }
+        record.set_topic(self.topic);
         Ok(new_instance)
     }
 }
@@ -228,6 +518,7 @@ impl From<Record> for RecordJS {
     fn from(record: Record) -> Self {
         Self {
             inner: Some(Arc::new(record)),
+            topic: None,
         }
     }
 }
@@ -237,12 +528,11 @@ impl fmt::Debug for RecordJS {
         let key = self
             .inner
             .as_ref()
-            .unwrap()
-            .key()
-            .is_some()
-            .then_some("Some(<Key>)");
+            .and_then(|record| record.key())
+            .map(|_| "Some(<Key>)");
 
         f.debug_struct("RecordJS")
+            .field("topic", &self.topic)
             .field("key", &key)
             .field("value", &"<Value>")
             .finish()
@@ -251,31 +541,288 @@ impl fmt::Debug for RecordJS {
 
 use fluvio::dataplane::link::ErrorCode;
 type PartitionConsumerIteratorInner = Pin<Box<dyn Stream<Item = Result<Record, ErrorCode>> + Send>>;
+type ConsumerStreamInner = Box<dyn OffsetStream + Send>;
+
+/// A `ConsumerStream` that can be boxed. `ConsumerStream::offset_flush`
+/// returns `impl Future`, which trait objects can't, so it is boxed here.
+pub trait OffsetStream: Stream<Item = Result<Record, ErrorCode>> + Unpin {
+    fn offset_commit(&mut self) -> Result<(), ErrorCode>;
+
+    fn offset_flush(&mut self) -> BoxFuture<'_, Result<(), ErrorCode>>;
+}
+
+impl<S: fluvio::consumer::ConsumerStream> OffsetStream for S {
+    fn offset_commit(&mut self) -> Result<(), ErrorCode> {
+        fluvio::consumer::ConsumerStream::offset_commit(self)
+    }
+
+    fn offset_flush(&mut self) -> BoxFuture<'_, Result<(), ErrorCode>> {
+        fluvio::consumer::ConsumerStream::offset_flush(self).boxed()
+    }
+}
+
+/// Stream backing a `PartitionConsumerIterator`.
+/// Only streams opened with the consumer config API can manage offsets.
+pub enum IteratorStream {
+    Partition(PartitionConsumerIteratorInner),
+    Consumer(ConsumerStreamInner),
+}
+
+impl Stream for IteratorStream {
+    type Item = Result<Record, ErrorCode>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match self.get_mut() {
+            Self::Partition(stream) => stream.as_mut().poll_next(cx),
+            Self::Consumer(stream) => Pin::new(stream).poll_next(cx),
+        }
+    }
+}
+
+/// State of a `PartitionConsumerIterator`, used by one call at a time
+struct IteratorState {
+    stream: Option<IteratorStream>,
+    // Offset requests from `commit` and `flush`, for streams that manage offsets
+    requests: Option<UnboundedReceiver<OffsetRequest>>,
+    // Error hit by `next_batch` after records were already collected,
+    // reported on the following call so those records are not lost
+    pending_error: Option<ErrorCode>,
+    finished: bool,
+}
+
+impl IteratorState {
+    /// Drops the stream, closing it on the SPU side
+    fn close(&mut self) {
+        if self.stream.take().is_some() {
+            debug!("Iterator closed, dropping stream");
+        }
+        self.finished = true;
+    }
+
+    /// Waits for the next record of the stream, or returns `None` once `deadline` has elapsed
+    /// or `stop` is notified. Offset requests sent meanwhile are served while waiting.
+    async fn next_before<D: Future>(
+        &mut self,
+        mut deadline: Pin<&mut D>,
+        stop: &Signal,
+    ) -> Option<Option<Result<Record, ErrorCode>>> {
+        loop {
+            let stream = self.stream.as_mut()?;
+            let requests = &mut self.requests;
+            let step = poll_fn(|cx| {
+                if stop.poll_notified(cx).is_ready() {
+                    return Poll::Ready(None);
+                }
+                if let Some(requests) = requests.as_mut() {
+                    if let Poll::Ready(Some(request)) = Pin::new(requests).poll_next(cx) {
+                        return Poll::Ready(Some(StreamStep::Request(request)));
+                    }
+                }
+                if let Poll::Ready(next) = Pin::new(&mut *stream).poll_next(cx) {
+                    return Poll::Ready(Some(next.map_or(StreamStep::End, StreamStep::Next)));
+                }
+                deadline.as_mut().poll(cx).map(|_| None)
+            })
+            .await;
+
+            match step? {
+                StreamStep::Next(next) => return Some(Some(next)),
+                StreamStep::End => return Some(None),
+                StreamStep::Request(request) => self.serve(request).await,
+            }
+        }
+    }
+
+    /// Serves the offset requests sent while no read was pending
+    async fn serve_queued(&mut self) {
+        while let Some(Some(request)) = self
+            .requests
+            .as_mut()
+            .and_then(|requests| requests.next().now_or_never())
+        {
+            self.serve(request).await;
+        }
+    }
+
+    /// Runs `request` on the stream. Once the stream is closed the request is dropped,
+    /// cancelling its reply.
+    async fn serve(&mut self, request: OffsetRequest) {
+        let Some(IteratorStream::Consumer(stream)) = self.stream.as_mut() else {
+            return;
+        };
+        // The requester may have gone away, so replies are allowed to fail
+        match request {
+            OffsetRequest::Commit(reply) => {
+                let _ = reply.send(stream.offset_commit());
+            }
+            OffsetRequest::Flush(reply) => {
+                let _ = reply.send(stream.offset_flush().await);
+            }
+        }
+    }
+}
 
 pub struct PartitionConsumerIterator {
-    inner: Option<PartitionConsumerIteratorInner>,
+    // Calls from JS may overlap, so each one locks the state it uses
+    state: AsyncMutex<IteratorState>,
+    // Reaches the state even while a read holds it
+    offsets: Option<OffsetHandle>,
+    topic: Option<Arc<str>>,
+    // Notified by `close`, cancelling a pending `next` or `next_batch`
+    closed: Signal,
 }
 
 #[node_bindgen]
 impl PartitionConsumerIterator {
     #[node_bindgen(constructor)]
     pub fn new() -> Self {
-        Self { inner: None }
+        Self {
+            state: AsyncMutex::new(IteratorState {
+                stream: None,
+                requests: None,
+                pending_error: None,
+                finished: false,
+            }),
+            offsets: None,
+            topic: None,
+            closed: Signal::default(),
+        }
     }
-    pub fn set_inner(&mut self, client: PartitionConsumerIteratorInner) {
-        self.inner.replace(client);
+    pub fn set_inner(&mut self, client: IteratorStream) {
+        let state = self.state.get_mut();
+        if let IteratorStream::Consumer(_) = client {
+            let (handle, requests) = OffsetHandle::channel();
+            self.offsets = Some(handle);
+            state.requests = Some(requests);
+        }
+        state.stream.replace(client);
+    }
+
+    pub fn set_topic(&mut self, topic: Arc<str>) {
+        self.topic.replace(topic);
+    }
+
+    /// Locks the state, dropping the stream if `close` was called meanwhile
+    async fn lock_state(&self) -> AsyncMutexGuard<'_, IteratorState> {
+        let mut state = self.state.lock().await;
+        if self.closed.is_notified() {
+            state.close();
+        }
+        state
+    }
+
+    #[node_bindgen]
+    async fn next(&self) -> Result<IterItem, FluvioErrorJS> {
+        let mut state = self.lock_state().await;
+        let next: Option<Result<Record, _>> = state
+            .next_before(pin!(pending::<()>()), &self.closed)
+            .await
+            .flatten();
+        if self.closed.is_notified() {
+            state.close();
+        }
+        let next: Option<Record> = next.transpose()?;
+        let next: Option<RecordJS> =
+            next.map(|record| RecordJS::from(record).with_topic(self.topic.clone()));
+        let next: IterItem = IterItem::from(next);
+        Ok(next)
     }
 
+    /// Pulls up to `max_records` records, returning early once `max_wait_ms` has elapsed.
+    /// The batch is empty if no record arrived in time, and `done` once the stream has ended.
     #[node_bindgen]
-    async fn next(&mut self) -> Result<IterItem, FluvioErrorJS> {
-        if let Some(ref mut inner) = self.inner {
-            let next: Option<Result<Record, _>> = inner.next().await;
-            let next: Option<Record> = next.transpose()?;
-            let next: IterItem = IterItem::from(next);
-            Ok(next)
+    async fn next_batch(
+        &self,
+        max_records: u32,
+        max_wait_ms: u32,
+    ) -> Result<BatchItem, FluvioErrorJS> {
+        if max_records == 0 {
+            return Err(FluvioErrorJS::new(
+                "maxRecords must be greater than 0".to_owned(),
+            ));
+        }
+        let mut state = self.lock_state().await;
+        if let Some(error) = state.pending_error.take() {
+            return Err(error.into());
+        }
+
+        let mut records = Vec::new();
+        let mut pending_error = None;
+        let mut finished = state.finished;
+        if state.stream.is_some() && !finished {
+            let mut deadline = pin!(sleep(Duration::from_millis(max_wait_ms.into())));
+            while records.len() < max_records as usize {
+                match state.next_before(deadline.as_mut(), &self.closed).await {
+                    Some(Some(Ok(record))) => {
+                        records.push(RecordJS::from(record).with_topic(self.topic.clone()))
+                    }
+                    Some(Some(Err(error))) if records.is_empty() => return Err(error.into()),
+                    Some(Some(Err(error))) => {
+                        pending_error = Some(error);
+                        break;
+                    }
+                    Some(None) => {
+                        finished = true;
+                        break;
+                    }
+                    // Deadline elapsed or iterator closed
+                    None => break,
+                }
+            }
         } else {
-            Ok(None.into())
+            finished = true;
+        }
+        state.pending_error = pending_error;
+        state.finished = finished;
+        if self.closed.is_notified() {
+            state.close();
         }
+
+        let done = records.is_empty() && state.finished;
+        Ok(BatchItem { records, done })
+    }
+
+    /// Stops the iterator: a pending `next` or `next_batch` resolves as done,
+    /// then the stream is dropped. Later calls resolve as done.
+    #[node_bindgen]
+    async fn close(&self) -> Result<(), FluvioErrorJS> {
+        self.closed.notify();
+        self.state.lock().await.close();
+        Ok(())
+    }
+
+    /// Marks the last record returned by `next` as processed.
+    /// Requires a stream opened with a consumer id.
+    #[node_bindgen]
+    async fn commit(&self) -> Result<(), FluvioErrorJS> {
+        self.offset_request(OffsetRequest::Commit).await
+    }
+
+    /// Sends the committed offset to the cluster
+    #[node_bindgen]
+    async fn flush(&self) -> Result<(), FluvioErrorJS> {
+        self.offset_request(OffsetRequest::Flush).await
+    }
+
+    /// Queues `request` for the stream. A pending `next` or `next_batch` serves it
+    /// while waiting for records; otherwise it is served here once the state is free.
+    async fn offset_request(&self, request: OffsetRequestKind) -> Result<(), FluvioErrorJS> {
+        let reply: OffsetReply = self
+            .offsets
+            .as_ref()
+            .ok_or_else(|| FluvioErrorJS::new(OFFSET_MANAGEMENT_UNSUPPORTED_MSG.to_owned()))?
+            .send(request)
+            .ok_or_else(|| FluvioErrorJS::new(ITERATOR_CLOSED_MSG.to_owned()))?;
+
+        let result = match select(reply, self.state.lock()).await {
+            Either::Left((result, _)) => result,
+            Either::Right((mut state, reply)) => {
+                state.serve_queued().await;
+                reply.await
+            }
+        };
+        result.map_err(|_| FluvioErrorJS::new(ITERATOR_CLOSED_MSG.to_owned()))??;
+        Ok(())
     }
 }
 
@@ -284,16 +831,64 @@ impl TryIntoJs for PartitionConsumerIterator {
         debug!("converting PartitionConsumerJS to js");
         let new_instance = PartitionConsumerIterator::new_instance(js_env, vec![])?;
         debug!("instance created");
-        if let Some(inner) = self.inner {
-            PartitionConsumerIterator::unwrap_mut(js_env, new_instance)?.set_inner(inner);
+        let iterator = PartitionConsumerIterator::unwrap_mut(js_env, new_instance)?;
+        if let Some(inner) = self.state.into_inner().stream {
+            iterator.set_inner(inner);
+        }
+        if let Some(topic) = self.topic {
+            iterator.set_topic(topic);
         }
         Ok(new_instance)
     }
 }
 
-impl From<Option<Record>> for IterItem {
-    fn from(maybe_record: Option<Record>) -> Self {
-        let value = maybe_record.map(RecordJS::from);
+/// Event delivered to the callback passed to `PartitionConsumerJS::stream`
+#[derive(Debug)]
+pub enum StreamEventJS {
+    Record(RecordJS),
+    Error(FluvioErrorJS),
+    End,
+}
+
+impl TryIntoJs for StreamEventJS {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        let mut event = JsObject::create(js_env)?;
+
+        match self {
+            Self::Record(record) => {
+                event.set_property(EVENT_TYPE_KEY, js_env.create_string_utf8(EVENT_RECORD)?)?;
+                event.set_property(EVENT_RECORD_KEY, record.try_to_js(js_env)?)?;
+            }
+            Self::Error(error) => {
+                event.set_property(EVENT_TYPE_KEY, js_env.create_string_utf8(EVENT_ERROR)?)?;
+                event.set_property(EVENT_ERROR_KEY, error.try_to_js(js_env)?)?;
+            }
+            Self::End => {
+                event.set_property(EVENT_TYPE_KEY, js_env.create_string_utf8(EVENT_END)?)?;
+            }
+        }
+
+        event.try_to_js(js_env)
+    }
+}
+
+/// Result of `PartitionConsumerIterator::next_batch`, shaped like an iterator result
+pub struct BatchItem {
+    records: Vec<RecordJS>,
+    done: bool,
+}
+
+impl TryIntoJs for BatchItem {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        let mut item = JsObject::create(js_env)?;
+        item.set_property(ITER_VALUE_KEY, self.records.try_to_js(js_env)?)?;
+        item.set_property(ITER_DONE_KEY, self.done.try_to_js(js_env)?)?;
+        item.try_to_js(js_env)
+    }
+}
+
+impl From<Option<RecordJS>> for IterItem {
+    fn from(value: Option<RecordJS>) -> Self {
         let done = value.is_none();
         Self { value, done }
     }
@@ -346,20 +941,20 @@ impl JSValue<'_> for OffsetWrapper {
         debug!("convert fetch offset param");
         if let Ok(js_obj) = env.convert_to_rust::<JsObject>(js_value) {
             let offset_from = optional_property!("from", String, js_obj);
-            let offset_index = must_property!("index", i64, js_obj) as u32;
-
-            let offset = match offset_from {
-                Some(from) => match from.as_ref() {
-                    OFFSET_BEGINNING => Offset::from_beginning(offset_index),
-                    OFFSET_END => Offset::from_end(offset_index),
-                    _ => {
-                        return Err(NjError::Other(format!(
-                            "unknown offset type. Must be either {:?} or {:?}.",
-                            OFFSET_BEGINNING, OFFSET_END
-                        )))
-                    }
-                },
-                None => Offset::from_end(offset_index),
+            let offset_index = must_property!("index", OffsetIndex, js_obj).0;
+
+            let offset = match offset_from.as_deref().unwrap_or(OFFSET_END) {
+                OFFSET_BEGINNING => Offset::from_beginning(relative_index(offset_index)?),
+                OFFSET_END => Offset::from_end(relative_index(offset_index)?),
+                OFFSET_ABSOLUTE => {
+                    Offset::absolute(offset_index).map_err(|err| NjError::Other(err.to_string()))?
+                }
+                _ => {
+                    return Err(NjError::Other(format!(
+                        "unknown offset type. Must be one of {:?}, {:?} or {:?}.",
+                        OFFSET_BEGINNING, OFFSET_END, OFFSET_ABSOLUTE
+                    )))
+                }
             };
 
             Ok(Self(offset))
@@ -369,18 +964,99 @@ impl JSValue<'_> for OffsetWrapper {
     }
 }
 
-pub struct FetchablePartitionResponseWrapper(Option<FetchablePartitionResponse<RecordSet>>);
+/// Offset index given either as a JS number or a BigInt
+struct OffsetIndex(i64);
+
+impl JSValue<'_> for OffsetIndex {
+    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        if let Ok(index) = env.convert_to_rust::<BigInt>(js_value) {
+            return i64::try_from(&index).map(Self).map_err(|_| {
+                NjError::Other(format!("offset index {} does not fit in 64 bits", index))
+            });
+        }
+
+        let index = env.convert_to_rust::<f64>(js_value)?;
+        if index.fract() != 0.0 || index.abs() > MAX_SAFE_INTEGER {
+            return Err(NjError::Other(format!(
+                "offset index {} must be an integer; use a BigInt for values beyond 2^53",
+                index
+            )));
+        }
+        Ok(Self(index as i64))
+    }
+}
+
+/// Relative offsets count records from the beginning or the end of the partition
+fn relative_index(index: i64) -> Result<u32, NjError> {
+    u32::try_from(index).map_err(|_| {
+        NjError::Other(format!(
+            "relative offset index must be between 0 and {}, got {}",
+            u32::MAX,
+            index
+        ))
+    })
+}
+
+/// Bounds for a one-shot `PartitionConsumerJS::fetch`
+pub struct FetchConfigWrapper {
+    max_bytes: Option<i32>,
+    max_records: Option<usize>,
+}
+
+impl JSValue<'_> for FetchConfigWrapper {
+    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        debug!("convert fetch config param");
+        if let Ok(js_obj) = env.convert_to_rust::<JsObject>(js_value) {
+            let max_bytes = optional_property!(FETCH_MAX_BYTES_KEY, f64, js_obj)
+                .map(|max_bytes| positive_integer(FETCH_MAX_BYTES_KEY, max_bytes, i32::MAX as f64))
+                .transpose()?;
+            let max_records = optional_property!(FETCH_MAX_RECORDS_KEY, f64, js_obj)
+                .map(|max_records| {
+                    positive_integer(FETCH_MAX_RECORDS_KEY, max_records, MAX_SAFE_INTEGER)
+                })
+                .transpose()?;
+
+            Ok(Self {
+                max_bytes: max_bytes.map(|max_bytes| max_bytes as i32),
+                max_records: max_records.map(|max_records| max_records as usize),
+            })
+        } else {
+            Err(NjError::Other("must pass json param".to_owned()))
+        }
+    }
+}
+
+/// Checks that an option given as a JS number is an integer in `1..=max`
+fn positive_integer(key: &str, value: f64, max: f64) -> Result<f64, NjError> {
+    if value.fract() != 0.0 || !(1.0..=max).contains(&value) {
+        return Err(NjError::Other(format!(
+            "{} must be a positive integer no greater than {}",
+            key, max
+        )));
+    }
+    Ok(value)
+}
+
+/// Fetched records, and the offset following the last of them if any were fetched
+pub struct FetchablePartitionResponseWrapper(
+    Option<FetchablePartitionResponse<RecordSet>>,
+    Option<i64>,
+);
 
 #[node_bindgen]
 impl<'a> FetchablePartitionResponseWrapper {
     #[node_bindgen(constructor)]
     fn new() -> Self {
-        Self(None)
+        Self(None, None)
     }
     fn set_inner(&mut self, inner: Option<FetchablePartitionResponse<RecordSet>>) {
         self.0 = inner;
     }
 
+    fn set_next_offset(&mut self, next_offset: Option<i64>) {
+        self.1 = next_offset;
+    }
+
     #[node_bindgen(getter)]
     fn partition_index(&self) -> Option<u32> {
         Some(self.0.as_ref()?.partition_index)
@@ -391,6 +1067,12 @@ impl<'a> FetchablePartitionResponseWrapper {
         Some(self.0.as_ref()?.high_watermark)
     }
 
+    /// Offset to fetch from to continue after this response
+    #[node_bindgen(getter)]
+    fn next_offset(&self) -> Option<i64> {
+        self.1
+    }
+
     #[node_bindgen(getter)]
     fn last_stable_offset(&self) -> Option<i64> {
         Some(self.0.as_ref()?.high_watermark)
@@ -458,7 +1140,9 @@ impl TryIntoJs for FetchablePartitionResponseWrapper {
     fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
         debug!("converting FluvioWrapper to js");
         let new_instance = FetchablePartitionResponseWrapper::new_instance(js_env, vec![])?;
-        FetchablePartitionResponseWrapper::unwrap_mut(js_env, new_instance)?.set_inner(self.0);
+        let response = FetchablePartitionResponseWrapper::unwrap_mut(js_env, new_instance)?;
+        response.set_inner(self.0);
+        response.set_next_offset(self.1);
         debug!("instance created");
         Ok(new_instance)
     }
@@ -0,0 +1,153 @@
+use std::future::{Future, poll_fn};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll};
+
+use futures_channel::{mpsc, oneshot};
+use futures_util::task::AtomicWaker;
+
+use fluvio::dataplane::link::ErrorCode;
+use fluvio_future::task::spawn;
+use fluvio_future::io::Stream;
+
+/// One-shot notification that one task at a time waits on.
+/// Only the last waker registered is kept, so waiters that go away leave nothing behind.
+#[derive(Default)]
+pub struct Signal {
+    notified: AtomicBool,
+    waker: AtomicWaker,
+}
+
+impl Signal {
+    pub fn notify(&self) {
+        self.notified.store(true, Ordering::SeqCst);
+        self.waker.wake();
+    }
+
+    pub fn is_notified(&self) -> bool {
+        self.notified.load(Ordering::SeqCst)
+    }
+
+    pub fn poll_notified(&self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.is_notified() {
+            return Poll::Ready(());
+        }
+
+        self.waker.register(cx.waker());
+        // Check again after registering so a concurrent `notify` is not missed
+        if self.is_notified() {
+            return Poll::Ready(());
+        }
+        Poll::Pending
+    }
+
+    pub async fn notified(&self) {
+        poll_fn(|cx| self.poll_notified(cx)).await
+    }
+}
+
+/// Notifies the signal when dropped, so waiters are released even if the task panics
+struct NotifyOnDrop(Arc<Signal>);
+
+impl Drop for NotifyOnDrop {
+    fn drop(&mut self) {
+        self.0.notify();
+    }
+}
+
+/// Waits for the next item of `stream`, or returns `None` once `stop` is notified
+pub async fn next_until<S>(stream: &mut S, stop: &Signal) -> Option<S::Item>
+where
+    S: Stream + Unpin,
+{
+    poll_fn(|cx| {
+        if stop.poll_notified(cx).is_ready() {
+            return Poll::Ready(None);
+        }
+        Pin::new(&mut *stream).poll_next(cx)
+    })
+    .await
+}
+
+/// Handle to a spawned stream task that can be stopped from JS
+pub struct StreamTask {
+    stop: Arc<Signal>,
+    done: Arc<Signal>,
+}
+
+impl StreamTask {
+    /// Spawns the future built by `task`, which must return once the given stop signal fires
+    pub fn spawn<T, F>(task: T) -> Self
+    where
+        T: FnOnce(Arc<Signal>) -> F,
+        F: Future + Send + 'static,
+        F::Output: Send,
+    {
+        let stop = Arc::new(Signal::default());
+        let done = Arc::new(Signal::default());
+
+        let future = task(stop.clone());
+        let guard = NotifyOnDrop(done.clone());
+        spawn(async move {
+            let _guard = guard;
+            future.await
+        });
+
+        Self { stop, done }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.done.is_notified()
+    }
+
+    /// Signals the task to stop and waits until it has fully finished
+    pub async fn stop(self) {
+        self.stop.notify();
+        self.done.notified().await;
+    }
+}
+
+/// Offset operation a stream task runs on the stream it owns
+pub enum OffsetRequest {
+    Commit(oneshot::Sender<Result<(), ErrorCode>>),
+    Flush(oneshot::Sender<Result<(), ErrorCode>>),
+}
+
+/// Builds an `OffsetRequest` around the sender of its reply
+pub type OffsetRequestKind = fn(oneshot::Sender<Result<(), ErrorCode>>) -> OffsetRequest;
+
+/// Reply to an `OffsetRequest`, cancelled if the request is dropped unserved
+pub type OffsetReply = oneshot::Receiver<Result<(), ErrorCode>>;
+
+/// Sends offset requests to whatever owns the stream: a stream task or an iterator
+#[derive(Clone)]
+pub struct OffsetHandle(mpsc::UnboundedSender<OffsetRequest>);
+
+impl OffsetHandle {
+    pub fn channel() -> (Self, mpsc::UnboundedReceiver<OffsetRequest>) {
+        let (sender, receiver) = mpsc::unbounded();
+        (Self(sender), receiver)
+    }
+
+    pub async fn commit(&self) -> Option<Result<(), ErrorCode>> {
+        self.request(OffsetRequest::Commit).await
+    }
+
+    pub async fn flush(&self) -> Option<Result<(), ErrorCode>> {
+        self.request(OffsetRequest::Flush).await
+    }
+
+    /// Returns `None` if the task finished before serving the request
+    async fn request(&self, request: OffsetRequestKind) -> Option<Result<(), ErrorCode>> {
+        self.send(request)?.await.ok()
+    }
+
+    /// Queues `request` without waiting for its reply.
+    /// Returns `None` if the receiving end is gone.
+    pub fn send(&self, request: OffsetRequestKind) -> Option<OffsetReply> {
+        let (sender, receiver) = oneshot::channel();
+        self.0.unbounded_send(request(sender)).ok()?;
+        Some(receiver)
+    }
+}
@@ -0,0 +1,216 @@
+use std::sync::Arc;
+
+use tracing::debug;
+use anyhow::Result;
+
+use fluvio::{Fluvio, Offset};
+use fluvio::consumer::{ConsumerConfigExt, ConsumerStream, Record};
+use fluvio::dataplane::link::ErrorCode;
+
+use node_bindgen::derive::node_bindgen;
+use node_bindgen::core::NjError;
+use node_bindgen::core::val::JsEnv;
+use node_bindgen::core::TryIntoJs;
+use node_bindgen::sys::napi_value;
+use node_bindgen::core::JSClass;
+
+use crate::CLIENT_NOT_FOUND_ERROR_MSG;
+use crate::error::FluvioErrorJS;
+
+use super::task::{OffsetHandle, StreamTask};
+use super::{
+    ConfigWrapper, IteratorStream, OffsetConfigWrapper, OffsetWrapper, PartitionConsumerIterator,
+    StreamEventJS, consumer_stream_events,
+};
+
+const NO_RUNNING_STREAM_MSG: &str = "no stream is running; start one with stream first";
+
+/// A topic and the partitions of it that are consumed together
+pub struct TopicConsumer {
+    client: Arc<Fluvio>,
+    topic: String,
+    partitions: Vec<u32>,
+    offsets: OffsetConfigWrapper,
+}
+
+impl TopicConsumer {
+    /// An empty `partitions` list selects every partition of the topic
+    pub fn new(
+        client: Arc<Fluvio>,
+        topic: String,
+        partitions: Vec<u32>,
+        offsets: OffsetConfigWrapper,
+    ) -> Self {
+        Self {
+            client,
+            topic,
+            partitions,
+            offsets,
+        }
+    }
+
+    async fn stream(
+        &self,
+        offset: Offset,
+        config: Option<ConfigWrapper>,
+    ) -> Result<impl ConsumerStream<Item = Result<Record, ErrorCode>>> {
+        let mut builder = ConsumerConfigExt::builder();
+        builder.topic(self.topic.clone()).offset_start(offset);
+        for partition in &self.partitions {
+            builder.partition(*partition);
+        }
+        self.offsets.apply(&mut builder);
+        if let Some(config) = config {
+            config.apply(&mut builder);
+        }
+
+        let config = builder.build()?;
+        debug!("Opening stream for topic {}", self.topic);
+        self.client.consumer_with_config(config).await
+    }
+}
+
+impl TryIntoJs for TopicConsumerJS {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        debug!("converting TopicConsumerJS to js");
+        let new_instance = TopicConsumerJS::new_instance(js_env, vec![])?;
+        debug!("instance created");
+        if let Some(inner) = self.inner {
+            TopicConsumerJS::unwrap_mut(js_env, new_instance)?.set_client(inner);
+        }
+        Ok(new_instance)
+    }
+}
+
+pub struct TopicConsumerJS {
+    inner: Option<TopicConsumer>,
+    // Streams started with `stream`, with the handle serving their offset requests
+    tasks: Vec<(StreamTask, OffsetHandle)>,
+}
+
+impl From<TopicConsumer> for TopicConsumerJS {
+    fn from(inner: TopicConsumer) -> Self {
+        Self {
+            inner: Some(inner),
+            tasks: Vec::new(),
+        }
+    }
+}
+
+#[node_bindgen]
+impl TopicConsumerJS {
+    #[node_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self {
+            inner: None,
+            tasks: Vec::new(),
+        }
+    }
+
+    pub fn set_client(&mut self, client: TopicConsumer) {
+        self.inner.replace(client);
+    }
+
+    fn client(&self) -> Result<&TopicConsumer, FluvioErrorJS> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_string()))
+    }
+
+    fn offset_handles(&self) -> Result<Vec<OffsetHandle>, FluvioErrorJS> {
+        let handles: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|(task, _)| !task.is_finished())
+            .map(|(_, handle)| handle.clone())
+            .collect();
+        if handles.is_empty() {
+            return Err(FluvioErrorJS::new(NO_RUNNING_STREAM_MSG.to_owned()));
+        }
+        Ok(handles)
+    }
+
+    #[node_bindgen(mt)]
+    async fn stream<F: Fn(StreamEventJS) + 'static + Send + Sync>(
+        &mut self,
+        offset: OffsetWrapper,
+        cb: F,
+    ) -> Result<(), FluvioErrorJS> {
+        let client = self.client()?;
+        let topic: Arc<str> = client.topic.as_str().into();
+        let stream = client.stream(offset.0, None).await?;
+
+        let (handle, requests) = OffsetHandle::channel();
+        // Forget about streams that already ended on their own
+        self.tasks.retain(|(task, _)| !task.is_finished());
+        let task = StreamTask::spawn(move |stop| {
+            consumer_stream_events(stream, topic, cb, stop, requests)
+        });
+        self.tasks.push((task, handle));
+        Ok(())
+    }
+
+    /// Marks the last record delivered to the callback of each running `stream` as processed.
+    /// Requires a consumer id and the manual offset strategy.
+    #[node_bindgen]
+    async fn commit(&self) -> Result<(), FluvioErrorJS> {
+        for handle in self.offset_handles()? {
+            // Streams that ended meanwhile have nothing left to commit
+            if let Some(result) = handle.commit().await {
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Sends the committed offsets of each running `stream` to the cluster
+    #[node_bindgen]
+    async fn flush(&self) -> Result<(), FluvioErrorJS> {
+        for handle in self.offset_handles()? {
+            if let Some(result) = handle.flush().await {
+                result?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Stops every stream started with `stream`, resolving once their tasks have finished
+    #[node_bindgen]
+    async fn end_stream(&mut self) -> Result<(), FluvioErrorJS> {
+        let tasks = std::mem::take(&mut self.tasks);
+        debug!("Stopping {} streams", tasks.len());
+        for (task, _) in tasks {
+            task.stop().await;
+        }
+        Ok(())
+    }
+
+    #[node_bindgen]
+    async fn create_stream(
+        &self,
+        offset: OffsetWrapper,
+    ) -> Result<PartitionConsumerIterator, FluvioErrorJS> {
+        let client = self.client()?;
+        let stream = client.stream(offset.0, None).await?;
+
+        let mut iterator = PartitionConsumerIterator::new();
+        iterator.set_inner(IteratorStream::Consumer(Box::new(stream)));
+        iterator.set_topic(client.topic.as_str().into());
+        Ok(iterator)
+    }
+
+    #[node_bindgen]
+    async fn stream_with_config(
+        &self,
+        offset: OffsetWrapper,
+        config: ConfigWrapper,
+    ) -> Result<PartitionConsumerIterator, FluvioErrorJS> {
+        let client = self.client()?;
+        let stream = client.stream(offset.0, Some(config)).await?;
+
+        let mut iterator = PartitionConsumerIterator::new();
+        iterator.set_inner(IteratorStream::Consumer(Box::new(stream)));
+        iterator.set_topic(client.topic.as_str().into());
+        Ok(iterator)
+    }
+}
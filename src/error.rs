@@ -1,24 +1,209 @@
-use node_bindgen::derive::node_bindgen;
+use std::error::Error as StdError;
+use std::io::Error as IoError;
 
-#[node_bindgen]
-pub struct FluvioErrorJS(String);
+use fluvio::FluvioError;
+use fluvio::config::ConfigError;
+use fluvio::dataplane::link::ErrorCode;
+
+use node_bindgen::core::NjError;
+use node_bindgen::core::TryIntoJs;
+use node_bindgen::core::val::{JsEnv, JsObject};
+use node_bindgen::sys::napi_value;
+
+const CODE_KEY: &str = "code";
+const CODE_NAME_KEY: &str = "codeName";
+const KIND_KEY: &str = "kind";
+const RETRIABLE_KEY: &str = "retriable";
+const SOURCES_KEY: &str = "sources";
+
+/// Where an error originated, mirrored by `ErrorKind` in `index.ts`
+#[derive(Debug, Clone, Copy)]
+enum ErrorKind {
+    /// Invalid arguments or state detected by this client
+    Client,
+    Network,
+    Server,
+    Config,
+    SmartModule,
+}
+
+impl ErrorKind {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Client => "client",
+            Self::Network => "network",
+            Self::Server => "server",
+            Self::Config => "config",
+            Self::SmartModule => "smartmodule",
+        }
+    }
+}
+
+/// Error thrown into JS as an `Error` carrying `code`, `codeName`, `kind`, `retriable` and `sources`
+#[derive(Debug, Clone)]
+pub struct FluvioErrorJS {
+    message: String,
+    code: Option<(u32, &'static str)>,
+    kind: ErrorKind,
+    retriable: bool,
+    sources: Vec<String>,
+}
 
 impl FluvioErrorJS {
     pub fn new(inner: String) -> Self {
-        Self(inner)
+        Self {
+            message: inner,
+            code: None,
+            kind: ErrorKind::Client,
+            retriable: false,
+            sources: Vec::new(),
+        }
+    }
+
+    /// Error raised while loading or running a SmartModule
+    pub fn smartmodule(error: anyhow::Error) -> Self {
+        let mut js_error = Self::from(error);
+        js_error.kind = ErrorKind::SmartModule;
+        js_error
+    }
+
+    /// Classifies the error from the first recognized error in its source chain
+    fn classify(&mut self, error: &(dyn StdError + 'static)) {
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(code) = error.downcast_ref::<ErrorCode>() {
+                self.set_code(code);
+                return;
+            }
+            if let Some(error) = error.downcast_ref::<FluvioError>() {
+                match error {
+                    FluvioError::Io(_) | FluvioError::Socket(_) => {
+                        self.kind = ErrorKind::Network;
+                        self.retriable = true;
+                    }
+                    FluvioError::ClientConfig(_) | FluvioError::ConsumerConfig(_) => {
+                        self.kind = ErrorKind::Config;
+                    }
+                    FluvioError::SmartModuleRuntime(_) => {
+                        self.kind = ErrorKind::SmartModule;
+                    }
+                    FluvioError::AdminApi(_) | FluvioError::TopicNotFound(_) => {
+                        self.kind = ErrorKind::Server;
+                    }
+                    // The source of other variants may still be recognized
+                    _ => {
+                        current = error.source();
+                        continue;
+                    }
+                }
+                return;
+            }
+            if error.downcast_ref::<ConfigError>().is_some() {
+                self.kind = ErrorKind::Config;
+                return;
+            }
+            if error.downcast_ref::<IoError>().is_some() {
+                self.kind = ErrorKind::Network;
+                self.retriable = true;
+                return;
+            }
+            current = error.source();
+        }
     }
+
+    fn set_code(&mut self, code: &ErrorCode) {
+        let js_code = js_error_code(code);
+        self.kind = match js_code {
+            Some((_, name)) if name.starts_with("SmartModule") => ErrorKind::SmartModule,
+            _ => ErrorKind::Server,
+        };
+        self.retriable = is_retriable(code);
+        self.code = js_code;
+    }
+}
+
+/// Value and name of the `ErrorCode` variant, mirrored by `ErrorCode` in `index.ts`.
+/// Codes missing there have neither here.
+fn js_error_code(code: &ErrorCode) -> Option<(u32, &'static str)> {
+    let js_code = match code {
+        ErrorCode::UnknownServerError => (0, "UnknownServerError"),
+        ErrorCode::None => (1, "None"),
+        ErrorCode::OffsetOutOfRange => (2, "OffsetOutOfRange"),
+        ErrorCode::NotLeaderForPartition => (3, "NotLeaderForPartition"),
+        ErrorCode::StorageError => (4, "StorageError"),
+        ErrorCode::SpuError => (5, "SpuError"),
+        ErrorCode::SpuRegisterationFailed => (6, "SpuRegisterationFailed"),
+        ErrorCode::SpuOffline => (7, "SpuOffline"),
+        ErrorCode::SpuNotFound => (8, "SpuNotFound"),
+        ErrorCode::SpuAlreadyExists => (9, "SpuAlreadyExists"),
+        ErrorCode::TopicError => (10, "TopicError"),
+        ErrorCode::TopicNotFound => (11, "TopicNotFound"),
+        ErrorCode::TopicAlreadyExists => (12, "TopicAlreadyExists"),
+        ErrorCode::TopicPendingInitialization => (13, "TopicPendingInitialization"),
+        ErrorCode::TopicInvalidConfiguration => (14, "TopicInvalidConfiguration"),
+        ErrorCode::PartitionPendingInitialization => (15, "PartitionPendingInitialization"),
+        ErrorCode::PartitionNotLeader => (16, "PartitionNotLeader"),
+        ErrorCode::SmartModuleNotFound { .. } => (17, "SmartModuleNotFound"),
+        ErrorCode::SmartModuleInvalid { .. } => (18, "SmartModuleInvalid"),
+        ErrorCode::SmartModuleInvalidExports { .. } => (19, "SmartModuleInvalidExports"),
+        ErrorCode::SmartModuleRuntimeError { .. } => (20, "SmartModuleRuntimeError"),
+        ErrorCode::SmartModuleChainInitError { .. } => (21, "SmartModuleChainInitError"),
+        ErrorCode::SmartModuleInitError { .. } => (22, "SmartModuleInitError"),
+        ErrorCode::SmartModuleLookBackError { .. } => (23, "SmartModuleLookBackError"),
+        ErrorCode::Other { .. } => (24, "Other"),
+        _ => return None,
+    };
+    Some(js_code)
+}
+
+/// Codes that describe a transient cluster state, worth retrying
+fn is_retriable(code: &ErrorCode) -> bool {
+    matches!(
+        code,
+        ErrorCode::NotLeaderForPartition
+            | ErrorCode::PartitionNotLeader
+            | ErrorCode::PartitionPendingInitialization
+            | ErrorCode::TopicPendingInitialization
+            | ErrorCode::SpuOffline
+    )
 }
 
 impl From<anyhow::Error> for FluvioErrorJS {
     fn from(error: anyhow::Error) -> Self {
-        Self(error.to_string())
+        let mut js_error = Self::new(error.to_string());
+        js_error.sources = error.chain().skip(1).map(|e| e.to_string()).collect();
+        js_error.classify(&*error);
+        js_error
     }
 }
 
-use fluvio::dataplane::link::ErrorCode;
+impl From<FluvioError> for FluvioErrorJS {
+    fn from(error: FluvioError) -> Self {
+        Self::from(anyhow::Error::from(error))
+    }
+}
 
 impl From<ErrorCode> for FluvioErrorJS {
     fn from(inner: ErrorCode) -> Self {
-        Self(inner.to_string())
+        let mut js_error = Self::new(inner.to_string());
+        js_error.set_code(&inner);
+        js_error
+    }
+}
+
+impl TryIntoJs for FluvioErrorJS {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        let error = js_env.create_error(&self.message)?;
+        let mut js_error = JsObject::new(*js_env, error);
+
+        if let Some((code, name)) = self.code {
+            js_error.set_property(CODE_KEY, code.try_to_js(js_env)?)?;
+            js_error.set_property(CODE_NAME_KEY, js_env.create_string_utf8(name)?)?;
+        }
+        js_error.set_property(KIND_KEY, js_env.create_string_utf8(self.kind.as_str())?)?;
+        js_error.set_property(RETRIABLE_KEY, self.retriable.try_to_js(js_env)?)?;
+        js_error.set_property(SOURCES_KEY, self.sources.try_to_js(js_env)?)?;
+
+        Ok(error)
     }
 }
@@ -1,18 +1,15 @@
-use std::time::Duration;
+use std::sync::Arc;
 
 use crate::CLIENT_NOT_FOUND_ERROR_MSG;
 use crate::admin::FluvioAdminJS;
-use crate::consumer::PartitionConsumerJS;
-use crate::producer::TopicProducerJS;
+use crate::consumer::{OffsetConfigWrapper, PartitionConsumerJS, TopicConsumer, TopicConsumerJS};
+use crate::producer::{TopicProducer, TopicProducerConfigWrapper, TopicProducerJS};
 use crate::error::FluvioErrorJS;
 
-use fluvio::TopicProducerConfig;
-use fluvio::Compression;
-use fluvio::TopicProducerConfigBuilder;
-use node_bindgen::core::JSValue;
 use tracing::debug;
 
 use fluvio::Fluvio;
+use fluvio::dataplane::record::ReplicaKey;
 
 use node_bindgen::derive::node_bindgen;
 use node_bindgen::core::TryIntoJs;
@@ -21,12 +18,19 @@ use node_bindgen::core::val::JsEnv;
 use node_bindgen::sys::napi_value;
 use node_bindgen::core::JSClass;
 use node_bindgen::core::val::JsObject;
+use node_bindgen::core::bigint::BigInt;
 
-use crate::{optional_property};
+const CONSUMER_ID_KEY: &str = "consumerId";
+const TOPIC_KEY: &str = "topic";
+const PARTITION_KEY: &str = "partition";
+const OFFSET_KEY: &str = "offset";
+const MODIFIED_TIME_KEY: &str = "modifiedTime";
 
 impl From<Fluvio> for FluvioJS {
     fn from(inner: Fluvio) -> Self {
-        Self { inner: Some(inner) }
+        Self {
+            inner: Some(Arc::new(inner)),
+        }
     }
 }
 
@@ -43,7 +47,7 @@ impl TryIntoJs for FluvioJS {
 }
 
 pub struct FluvioJS {
-    inner: Option<Fluvio>,
+    inner: Option<Arc<Fluvio>>,
 }
 
 #[node_bindgen]
@@ -53,7 +57,7 @@ impl FluvioJS {
         Self { inner: None }
     }
 
-    pub fn set_client(&mut self, client: Fluvio) {
+    pub fn set_client(&mut self, client: Arc<Fluvio>) {
         self.inner.replace(client);
     }
 
@@ -75,9 +79,66 @@ impl FluvioJS {
     ) -> Result<PartitionConsumerJS, FluvioErrorJS> {
         if let Some(client) = &mut self.inner {
             #[allow(deprecated)]
-            Ok(PartitionConsumerJS::from(
-                client.partition_consumer(topic, partition).await?,
-            ))
+            let consumer = client.partition_consumer(topic, partition).await?;
+            Ok(PartitionConsumerJS::with_fluvio(consumer, client.clone()))
+        } else {
+            Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
+        }
+    }
+
+    /// Consumes `partitions` of `topic` as one merged stream,
+    /// or every partition of the topic when `partitions` is empty
+    #[node_bindgen]
+    async fn topic_consumer(
+        &mut self,
+        topic: String,
+        partitions: Vec<u32>,
+        offsets: OffsetConfigWrapper,
+    ) -> Result<TopicConsumerJS, FluvioErrorJS> {
+        if let Some(client) = &mut self.inner {
+            Ok(TopicConsumerJS::from(TopicConsumer::new(
+                client.clone(),
+                topic,
+                partitions,
+                offsets,
+            )))
+        } else {
+            Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
+        }
+    }
+
+    /// Lists the offsets stored on the cluster for every consumer id
+    #[node_bindgen]
+    async fn consumer_offsets(&mut self) -> Result<Vec<ConsumerOffsetJS>, FluvioErrorJS> {
+        if let Some(client) = &mut self.inner {
+            let offsets = client.consumer_offsets().await?;
+            Ok(offsets
+                .into_iter()
+                .map(|offset| ConsumerOffsetJS {
+                    consumer_id: offset.consumer_id,
+                    topic: offset.topic,
+                    partition: offset.partition,
+                    offset: offset.offset,
+                    modified_time: offset.modified_time,
+                })
+                .collect())
+        } else {
+            Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
+        }
+    }
+
+    #[node_bindgen]
+    async fn delete_consumer_offset(
+        &mut self,
+        consumer_id: String,
+        topic: String,
+        partition: u32,
+    ) -> Result<(), FluvioErrorJS> {
+        if let Some(client) = &mut self.inner {
+            client
+                .delete_consumer_offset(consumer_id, ReplicaKey::new(topic, partition))
+                .await?;
+            Ok(())
         } else {
             Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
         }
@@ -86,7 +147,10 @@ impl FluvioJS {
     #[node_bindgen]
     async fn topic_producer(&mut self, topic: String) -> Result<TopicProducerJS, FluvioErrorJS> {
         if let Some(client) = &mut self.inner {
-            Ok(TopicProducerJS::from(client.topic_producer(topic).await?))
+            Ok(TopicProducerJS::from(
+                TopicProducer::new(client.clone(), topic, TopicProducerConfigWrapper::default())
+                    .await?,
+            ))
         } else {
             Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
         }
@@ -98,11 +162,9 @@ impl FluvioJS {
         topic: String,
         config: TopicProducerConfigWrapper,
     ) -> Result<TopicProducerJS, FluvioErrorJS> {
-        let config = config.inner;
-
         if let Some(client) = &mut self.inner {
             Ok(TopicProducerJS::from(
-                client.topic_producer_with_config(topic, config).await?,
+                TopicProducer::new(client.clone(), topic, config).await?,
             ))
         } else {
             Err(FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
@@ -110,75 +172,31 @@ impl FluvioJS {
     }
 }
 
-// TopicProducerConfigBuilder is used to create a TopicProducerConfig
-//
-// TopicProducerConfigBuilder::default()
-//     .compression(Compression::Lz4)
-//     .max_request_size(1024 * 1024)
-//     .batch_size(1024)
-//     .batch_queue_size(1024)
-//     .linger(Duration::from_millis(100))
-//     .build()
-//     .unwrap();
-
-pub struct TopicProducerConfigWrapper {
-    pub inner: TopicProducerConfig,
+/// Offset stored on the cluster for a consumer id and partition
+pub struct ConsumerOffsetJS {
+    consumer_id: String,
+    topic: String,
+    partition: u32,
+    offset: i64,
+    modified_time: u64,
 }
 
-impl JSValue<'_> for TopicProducerConfigWrapper {
-    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
-        debug!("converting topic producer config from JS");
-        if let Ok(js_obj) = env.convert_to_rust::<JsObject>(js_value) {
-            let mut builder = TopicProducerConfigBuilder::default();
-
-            // Extract compression if provided
-            if let Some(compression_str) = optional_property!("compression", String, js_obj) {
-                let compression = match compression_str.as_str() {
-                    "none" => Compression::None,
-                    "gzip" => Compression::Gzip,
-                    "snappy" => Compression::Snappy,
-                    "lz4" => Compression::Lz4,
-                    "zstd" => Compression::Zstd,
-                    _ => {
-                        return Err(NjError::Other(format!(
-                            "Invalid compression type: {}",
-                            compression_str
-                        )))
-                    }
-                };
-                builder = builder.compression(compression);
-            }
-
-            // Extract max_request_size if provided
-            if let Some(max_request_size) = optional_property!("maxRequestSize", i64, js_obj) {
-                builder = builder.max_request_size(max_request_size as usize);
-            }
-
-            // Extract batch_size if provided
-            if let Some(batch_size) = optional_property!("batchSize", i64, js_obj) {
-                builder = builder.batch_size(batch_size as usize);
-            }
-
-            // Extract batch_queue_size if provided
-            if let Some(batch_queue_size) = optional_property!("batchQueueSize", i64, js_obj) {
-                builder = builder.batch_queue_size(batch_queue_size as usize);
-            }
-
-            // Extract linger if provided (in milliseconds)
-            if let Some(linger_ms) = optional_property!("linger", i64, js_obj) {
-                builder = builder.linger(Duration::from_millis(linger_ms as u64));
-            }
-
-            // Build the config
-            match builder.build() {
-                Ok(config) => Ok(Self { inner: config }),
-                Err(err) => Err(NjError::Other(format!(
-                    "Failed to build TopicProducerConfig: {}",
-                    err
-                ))),
-            }
-        } else {
-            Err(NjError::Other("parameter must be a JSON object".to_owned()))
-        }
+impl TryIntoJs for ConsumerOffsetJS {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        let mut offset = JsObject::create(js_env)?;
+
+        offset.set_property(
+            CONSUMER_ID_KEY,
+            js_env.create_string_utf8(&self.consumer_id)?,
+        )?;
+        offset.set_property(TOPIC_KEY, js_env.create_string_utf8(&self.topic)?)?;
+        offset.set_property(PARTITION_KEY, self.partition.try_to_js(js_env)?)?;
+        offset.set_property(OFFSET_KEY, BigInt::from(self.offset).try_to_js(js_env)?)?;
+        offset.set_property(
+            MODIFIED_TIME_KEY,
+            BigInt::from(self.modified_time).try_to_js(js_env)?,
+        )?;
+
+        offset.try_to_js(js_env)
     }
 }
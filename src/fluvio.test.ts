@@ -1,11 +1,18 @@
 import Fluvio, {
+    ErrorCode,
     FluvioAdmin,
+    FluvioError,
     KeyValue,
     Offset,
+    Record,
     SmartModuleType,
+    testSmartModule,
 } from '../src/index'
 import { v4 as uuidV4 } from 'uuid'
 import fs from 'fs'
+import { Readable, Transform } from 'stream'
+import { once } from 'events'
+import { pipeline } from 'stream/promises'
 
 const topic_create_timeout = 10000
 async function sleep(ms: number) {
@@ -23,6 +30,71 @@ describe('Fluvio Admin', () => {
     })
 })
 
+describe('Fluvio Admin SmartModules', () => {
+    jest.setTimeout(100000) // 100 seconds
+    let admin: FluvioAdmin
+    let fluvio: Fluvio
+
+    beforeAll(async () => {
+        fluvio = await Fluvio.connect()
+        admin = await fluvio.admin()
+    })
+
+    test('Creates, finds, uses and deletes a SmartModule', async () => {
+        const name = `filter-${uuidV4()}`
+        const topic = uuidV4()
+        const wasm = await fs.promises.readFile('./fixtures/level_filter.wasm')
+
+        await admin.createSmartModule(name, {
+            data: wasm,
+            metadata: './fixtures/level-filter/SmartModule.toml',
+        })
+        const found = await admin.findSmartModule(name)
+        expect(found?.name).toEqual(name)
+        expect(found?.wasmSize).toBeGreaterThan(0)
+        expect(found?.meta?.package.name).toEqual('level-filter')
+        expect(found?.meta?.params).toEqual([
+            {
+                name: 'level',
+                description: 'Level of the log records to drop',
+                optional: false,
+            },
+        ])
+        const listed = await admin.listSmartModules()
+        expect(listed.map((smartmodule) => smartmodule.name)).toContain(name)
+
+        await admin.createTopic(topic)
+        await sleep(topic_create_timeout)
+        try {
+            const producer = await fluvio.topicProducer(topic)
+            const consumer = await fluvio.partitionConsumer(topic, 0)
+            await producer.sendAll([
+                [null, JSON.stringify({ level: 'debug', message: 'hidden' })],
+                [null, JSON.stringify({ level: 'info', message: 'shown' })],
+            ])
+            const stream = await consumer.streamWithConfig(
+                Offset.FromBeginning(),
+                {
+                    smartmoduleType: SmartModuleType.Filter,
+                    smartmoduleName: name,
+                    smartmoduleParams: { level: 'debug' },
+                }
+            )
+            for await (const record of stream) {
+                expect(JSON.parse(record.valueString()).message).toEqual(
+                    'shown'
+                )
+                break
+            }
+        } finally {
+            await admin.deleteTopic(topic)
+            await admin.deleteSmartModule(name)
+        }
+
+        expect(await admin.findSmartModule(name)).toBeUndefined()
+    })
+})
+
 describe('Fluvio Producer and Consume using AsyncIterator', () => {
     jest.setTimeout(100000) // 100 seconds
     let admin: FluvioAdmin
@@ -78,11 +150,86 @@ describe('Fluvio Producer and Consume using AsyncIterator', () => {
         const stream = await consumer.createStream(Offset.FromBeginning())
         for await (const record of stream) {
             expect(record.valueString()).toEqual(`Message: ${counter}`)
+            expect(Number(record.offset())).toEqual(counter)
+            expect(record.partition()).toEqual(0)
+            expect(record.topic()).toEqual(topic)
             counter++
             if (counter >= MAX_COUNT) break
         }
         expect(counter).toEqual(MAX_COUNT)
     })
+
+    test('Consume using a callback stream and stop it', async () => {
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const MAX_COUNT = 10
+        const received: string[] = []
+        const errors: FluvioError[] = []
+        let ended = false
+
+        await consumer.stream(
+            Offset.FromBeginning(),
+            (record) => received.push(record.valueString()),
+            {
+                onError: (error) => errors.push(error),
+                onEnd: () => {
+                    ended = true
+                },
+            }
+        )
+        while (received.length < MAX_COUNT) {
+            await sleep(100)
+        }
+        await consumer.endStream()
+        // Give the queued `end` event a chance to reach the JS thread
+        await sleep(100)
+
+        expect(ended).toBe(true)
+        expect(errors).toEqual([])
+        expect(received[0]).toEqual('Message: 0')
+    })
+
+    test('Consume in batches', async () => {
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const MAX_COUNT = 10
+        const batches = await consumer.createBatchStream(
+            Offset.FromBeginning(),
+            { maxRecords: 4, maxWaitMs: 500 }
+        )
+        const received: string[] = []
+        for await (const records of batches) {
+            expect(records.length).toBeLessThanOrEqual(4)
+            received.push(...records.map((record) => record.valueString()))
+            if (received.length >= MAX_COUNT) break
+        }
+        expect(received.slice(0, 3)).toEqual([
+            'Message: 0',
+            'Message: 1',
+            'Message: 2',
+        ])
+    })
+    test('Pipe records from a consumer Readable', async () => {
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const readable = await consumer.createReadable(Offset.FromBeginning(), {
+            highWaterMark: 2,
+        })
+        const values = new Transform({
+            objectMode: true,
+            transform(record: Record, _encoding, callback) {
+                callback(null, record.valueString())
+            },
+        })
+
+        const received: string[] = []
+        for await (const value of readable.pipe(values)) {
+            received.push(value)
+            if (received.length >= 3) break
+        }
+        readable.destroy()
+        await once(readable, 'close')
+
+        expect(received).toEqual(['Message: 0', 'Message: 1', 'Message: 2'])
+        expect(readable.destroyed).toBe(true)
+    })
 })
 
 describe('Fluvio Batch Producer', () => {
@@ -128,6 +275,449 @@ describe('Fluvio Batch Producer', () => {
         }
         expect(counter).toEqual(MAX_COUNT)
     })
+
+    test('Send records from Buffers and typed array views', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const bytes = Buffer.from('__view value__')
+        const view = new Uint8Array(bytes.buffer, bytes.byteOffset + 2, 10)
+        const first = await producer.send(Buffer.from('buffer key'), view)
+        await producer.send(
+            new DataView(bytes.buffer, bytes.byteOffset, 2),
+            Buffer.from('buffer value')
+        )
+
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const stream = await consumer.createStream(
+            Offset.Absolute(Number(first.offset))
+        )
+        const received: [string | null, string][] = []
+        for await (const record of stream) {
+            received.push([record.keyString(), record.valueString()])
+            if (received.length >= 2) break
+        }
+        expect(received).toEqual([
+            ['buffer key', 'view value'],
+            ['__', 'buffer value'],
+        ])
+    })
+
+    test('Sends all bytes of multi-byte typed arrays', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const value = new Uint16Array([0x6968, 0x2121])
+        const output = await producer.send('wide', value)
+
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const stream = await consumer.createStream(
+            Offset.Absolute(Number(output.offset))
+        )
+        for await (const record of stream) {
+            expect(Buffer.from(record.value())).toEqual(
+                Buffer.from(value.buffer)
+            )
+            break
+        }
+    })
+
+    test('Rejects objects that only look like views', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const fakeView = {
+            buffer: new ArrayBuffer(4),
+            byteOffset: -1,
+            byteLength: 2,
+        }
+        await expect(producer.send('key', fakeView as any)).rejects.toThrow(
+            'Producer args must be string, ArrayBuffer, TypedArray or DataView'
+        )
+    })
+
+    test('Queues records and waits for their metadata', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const outputs = await producer.queueAll([
+            ['queued', 'first'],
+            ['queued', 'second'],
+        ])
+        await producer.flush()
+
+        const metadata = await Promise.all(
+            outputs.map((output) => output.wait())
+        )
+        expect(metadata.map(({ partition }) => partition)).toEqual([0, 0])
+        expect(Number(metadata[1].offset)).toEqual(
+            Number(metadata[0].offset) + 1
+        )
+        // Waiting again resolves with the same metadata
+        expect(await outputs[0].wait()).toEqual(metadata[0])
+    })
+
+    test('Send records without a key', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const first = await producer.send(null, 'no key')
+        await producer.sendAll([
+            [undefined, 'no key either'],
+            ['key', 'with key'],
+        ])
+
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const stream = await consumer.createStream(
+            Offset.Absolute(Number(first.offset))
+        )
+        const keys: (string | null)[] = []
+        const hasKeys: boolean[] = []
+        for await (const record of stream) {
+            keys.push(record.keyString())
+            hasKeys.push(record.hasKey())
+            if (keys.length >= 3) break
+        }
+        expect(keys).toEqual([null, null, 'key'])
+        expect(hasKeys).toEqual([false, false, true])
+    })
+
+    test('Send records with at-least-once delivery', async () => {
+        const producer = await fluvio.topicProducerWithConfig(topic, {
+            lingerMs: 10,
+            timeoutMs: 5000,
+            isolation: 'read-committed',
+            retryPolicy: {
+                maxRetries: 3,
+                initialDelayMs: 10,
+                maxDelayMs: 1000,
+                strategy: 'exponential',
+            },
+        })
+        const metadata = await producer.send('key', 'delivered')
+        expect(metadata.partition).toEqual(0)
+
+        await expect(
+            fluvio.topicProducerWithConfig(topic, {
+                deliverySemantic: 'at-most-once',
+                retryPolicy: { maxRetries: 3 },
+            })
+        ).rejects.toThrow('retryPolicy can only be used')
+        await expect(
+            fluvio.topicProducerWithConfig(topic, { lingerMs: -1 })
+        ).rejects.toThrow('lingerMs must be a non-negative number')
+        await expect(
+            fluvio.topicProducerWithConfig(topic, {
+                retryPolicy: { initialDelayMs: -10 },
+            })
+        ).rejects.toThrow('initialDelayMs must be a non-negative number')
+        await expect(
+            fluvio.topicProducerWithConfig(topic, {
+                retryPolicy: { maxRetries: -1 },
+            })
+        ).rejects.toThrow('maxRetries must be a non-negative integer')
+    })
+    test('Pipe records into a producer Writable', async () => {
+        const producer = await fluvio.topicProducerWithConfig(topic, {
+            batchQueueSize: 2,
+        })
+        const first = await producer.send('start', 'writable')
+
+        const MAX_COUNT = 50
+        const chunks: (string | KeyValue)[] = []
+        for (let i = 0; i < MAX_COUNT; i++) {
+            chunks.push(i % 2 === 0 ? `written ${i}` : [`${i}`, `written ${i}`])
+        }
+        await pipeline(Readable.from(chunks), producer.createWritable())
+
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const stream = await consumer.createStream(
+            Offset.Absolute(Number(first.offset) + 1)
+        )
+        let counter = 0
+        for await (const record of stream) {
+            expect(record.valueString()).toEqual(`written ${counter}`)
+            expect(record.hasKey()).toEqual(counter % 2 === 1)
+            counter++
+            if (counter >= MAX_COUNT) break
+        }
+        expect(counter).toEqual(MAX_COUNT)
+    })
+
+    test('Holds back writes past `maxPendingWrites`', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const writable = producer.createWritable({
+            highWaterMark: 1,
+            maxPendingWrites: 1,
+        })
+
+        expect(writable.write('pending')).toEqual(false)
+        expect(writable.write('held back')).toEqual(false)
+        expect(writable.writableLength).toEqual(2)
+
+        await new Promise((resolve) => writable.once('drain', resolve))
+        expect(writable.writableLength).toEqual(0)
+        writable.end()
+        await new Promise((resolve) => writable.once('finish', resolve))
+    })
+})
+
+describe('Fluvio Topic Consumer', () => {
+    jest.setTimeout(100000) // 100 seconds
+    let admin: FluvioAdmin
+    let fluvio: Fluvio
+    let topic: string
+
+    beforeAll(async () => {
+        topic = uuidV4()
+        fluvio = await Fluvio.connect()
+        admin = await fluvio.admin()
+        console.log(`Creating topic ${topic}`)
+        await admin.createTopic(topic, {
+            partitions: 2,
+            replicationFactor: 1,
+            ignoreRackAssignment: false,
+        })
+        await sleep(topic_create_timeout)
+    })
+
+    afterAll(async () => {
+        console.log(`Deleting topic ${topic}`)
+        await admin.deleteTopic(topic)
+        await sleep(topic_create_timeout)
+    })
+
+    test('Consumes every partition as one stream', async () => {
+        const producer = await fluvio.topicProducer(topic)
+
+        const MAX_COUNT = 10
+        const records: KeyValue[] = []
+        for (let i = 0; i < MAX_COUNT; i++) {
+            records.push([`${i}`, `This is record ${i}`])
+        }
+        await producer.sendAll(records)
+        await producer.flush()
+
+        const consumer = await fluvio.topicConsumer(topic)
+        const stream = await consumer.createStream(Offset.FromBeginning())
+        const received: string[] = []
+        const partitions = new Set<number>()
+        for await (const record of stream) {
+            received.push(record.valueString())
+            partitions.add(record.partition())
+            if (received.length >= MAX_COUNT) break
+        }
+
+        expect(received.sort()).toEqual(
+            records.map(([_, value]) => value as string).sort()
+        )
+        partitions.forEach((partition) => expect(partition).toBeLessThan(2))
+    })
+
+    test('Sends to an explicit partition', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const sent = await producer.sendAll(
+            [
+                ['a', 'explicit a'],
+                ['b', 'explicit b'],
+            ],
+            1
+        )
+        const last = await producer.send('c', 'explicit c', 1)
+        await producer.flush()
+
+        expect(sent.map((metadata) => metadata.partition)).toEqual([1, 1])
+        expect(Number(sent[1].offset)).toEqual(Number(sent[0].offset) + 1)
+        expect(last.partition).toEqual(1)
+        expect(Number(last.offset)).toEqual(Number(sent[1].offset) + 1)
+
+        const consumer = await fluvio.partitionConsumer(topic, 1)
+        const response = await consumer.fetch(Offset.FromBeginning())
+        const values = response.toRecords()
+        expect(values).toEqual(
+            expect.arrayContaining(['explicit a', 'explicit b', 'explicit c'])
+        )
+
+        await expect(producer.send('d', 'nowhere', 2)).rejects.toMatchObject({
+            message: expect.stringContaining('not found'),
+        })
+    })
+
+    test('Routes records with a custom partitioner', async () => {
+        const byKey = await fluvio.topicProducerWithConfig(topic, {
+            partitioner: (key, _value, partitionCount) =>
+                key === 'tenant-b' ? partitionCount - 1 : 0,
+        })
+        const sent = await byKey.sendAll([
+            ['tenant-a', 'a'],
+            ['tenant-b', 'b'],
+            ['tenant-a', 'c'],
+        ])
+        expect(sent.map((metadata) => metadata.partition)).toEqual([0, 1, 0])
+
+        const roundRobin = await fluvio.topicProducerWithConfig(topic, {
+            partitioner: 'round-robin',
+        })
+        const spread = await roundRobin.sendAll([
+            ['k', 'first'],
+            ['k', 'second'],
+        ])
+        const partitions = spread.map((metadata) => metadata.partition)
+        expect(partitions.sort()).toEqual([0, 1])
+
+        const outOfRange = await fluvio.topicProducerWithConfig(topic, {
+            partitioner: (_key, _value, partitionCount) => partitionCount,
+        })
+        await expect(outOfRange.send('k', 'nowhere')).rejects.toThrow(
+            'partitioner returned 2'
+        )
+        await expect(outOfRange.sendAll([['k', 'nowhere']])).rejects.toThrow(
+            RangeError
+        )
+    })
+
+    test('Stores committed offsets for a consumer id', async () => {
+        const consumerId = `consumer-${uuidV4()}`
+        const consumer = await fluvio.topicConsumer(topic, [0], {
+            consumerId,
+            offsetStrategy: 'manual',
+        })
+        const stream = await consumer.createStream(Offset.FromBeginning())
+        for await (const _ of stream) {
+            await stream.commit()
+            await stream.flush()
+            break
+        }
+
+        const offsets = await fluvio.consumerOffsets()
+        const stored = offsets.find(
+            (offset) => offset.consumerId === consumerId
+        )
+        expect(stored?.topic).toEqual(topic)
+        expect(stored?.partition).toEqual(0)
+
+        await fluvio.deleteConsumerOffset(consumerId, topic, 0)
+        const remaining = await fluvio.consumerOffsets()
+        expect(
+            remaining.find((offset) => offset.consumerId === consumerId)
+        ).toBeUndefined()
+    })
+
+    test('Commits while the stream waits for records', async () => {
+        const consumerId = `consumer-${uuidV4()}`
+        const consumer = await fluvio.topicConsumer(topic, [0], {
+            consumerId,
+            offsetStrategy: 'manual',
+        })
+        const producer = await fluvio.topicProducer(topic)
+        const stream = await consumer.createStream(Offset.FromEnd())
+        const iterator = stream[Symbol.asyncIterator]()
+
+        await producer.send('key', 'before commit', 0)
+        expect((await iterator.next()).value.valueString()).toEqual(
+            'before commit'
+        )
+        const pending = iterator.next()
+        await stream.commit()
+        await stream.flush()
+
+        const offsets = await fluvio.consumerOffsets()
+        const stored = offsets.find(
+            (offset) => offset.consumerId === consumerId
+        )
+        expect(stored?.partition).toEqual(0)
+
+        await producer.send('key', 'after commit', 0)
+        expect((await pending).value.valueString()).toEqual('after commit')
+        await fluvio.deleteConsumerOffset(consumerId, topic, 0)
+    })
+
+    test('Commits offsets from a callback stream', async () => {
+        const consumerId = `consumer-${uuidV4()}`
+        const consumer = await fluvio.topicConsumer(topic, [0], {
+            consumerId,
+            offsetStrategy: 'manual',
+        })
+        const received = new Promise<void>((resolve) =>
+            consumer.stream(Offset.FromBeginning(), () => resolve())
+        )
+        await received
+        await consumer.commit()
+        await consumer.flush()
+        await consumer.endStream()
+
+        const offsets = await fluvio.consumerOffsets()
+        const stored = offsets.find(
+            (offset) => offset.consumerId === consumerId
+        )
+        expect(stored?.partition).toEqual(0)
+        await fluvio.deleteConsumerOffset(consumerId, topic, 0)
+
+        await expect(consumer.commit()).rejects.toMatchObject({
+            message: expect.stringContaining('no stream is running'),
+        })
+    })
+
+    test('Rejects a negative offset flush interval', async () => {
+        await expect(
+            fluvio.topicConsumer(topic, [0], {
+                consumerId: `consumer-${uuidV4()}`,
+                offsetFlushMs: -1,
+            })
+        ).rejects.toThrow('offsetFlushMs')
+    })
+})
+
+describe('Fluvio Fetch', () => {
+    jest.setTimeout(100000) // 100 seconds
+    let admin: FluvioAdmin
+    let fluvio: Fluvio
+    let topic: string
+
+    beforeAll(async () => {
+        topic = uuidV4()
+        fluvio = await Fluvio.connect()
+        admin = await fluvio.admin()
+        console.log(`Creating topic ${topic}`)
+        await admin.createTopic(topic)
+        await sleep(topic_create_timeout)
+    })
+
+    afterAll(async () => {
+        console.log(`Deleting topic ${topic}`)
+        await admin.deleteTopic(topic)
+        await sleep(topic_create_timeout)
+    })
+
+    test('Fetches a bounded slice of the partition', async () => {
+        const producer = await fluvio.topicProducer(topic)
+
+        const MAX_COUNT = 10
+        const records: KeyValue[] = []
+        for (let i = 0; i < MAX_COUNT; i++) {
+            records.push([`${i}`, `This is record ${i}`])
+        }
+        await producer.sendAll(records)
+        await producer.flush()
+
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const all = await consumer.fetch(Offset.FromBeginning())
+        expect(all.toRecords().length).toEqual(MAX_COUNT)
+
+        const slice = await consumer.fetch(Offset.FromBeginning(), {
+            maxRecords: 5,
+        })
+        expect(slice.toRecords()).toEqual(
+            records.slice(0, 5).map(([_, value]) => value)
+        )
+        expect(slice.nextOffset).toEqual(5)
+        expect(slice.highWatermark).toEqual(MAX_COUNT)
+
+        const resumed = await consumer.fetch(Offset.Absolute(5), {
+            maxRecords: 2,
+        })
+        expect(resumed.toRecords()).toEqual([
+            'This is record 5',
+            'This is record 6',
+        ])
+
+        await expect(
+            consumer.fetch(Offset.FromBeginning(), { maxRecords: 0 })
+        ).rejects.toThrow('maxRecords must be a positive integer')
+        await expect(
+            consumer.fetch(Offset.FromBeginning(), { maxBytes: -1 })
+        ).rejects.toThrow('maxBytes must be a positive integer')
+    })
 })
 
 describe('Configures a SmartModule', () => {
@@ -226,6 +816,274 @@ describe('Configures a SmartModule', () => {
         )
     })
 
+    test('Accepts plain and Gzipped SmartModules from files, Base64 and Buffers', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const serverLogsFile = await fs.promises.readFile(
+            './fixtures/server_log.json',
+            'utf8'
+        )
+        const serverLogs: { message: string; level: string }[] =
+            JSON.parse(serverLogsFile)
+        const plain = await fs.promises.readFile(
+            './fixtures/server_logs_filter.wasm'
+        )
+        const gzipped = await fs.promises.readFile(
+            './fixtures/server_logs_filter.wasm.gz'
+        )
+        const stream = await consumer.streamWithConfig(Offset.FromBeginning(), {
+            smartmodules: [
+                {
+                    type: SmartModuleType.Filter,
+                    file: './fixtures/server_logs_filter.wasm.gz',
+                },
+                { type: SmartModuleType.Filter, data: plain },
+                {
+                    type: SmartModuleType.Filter,
+                    data: plain.toString('base64'),
+                },
+                { type: SmartModuleType.Filter, data: gzipped },
+            ],
+        })
+        const expectedCount = serverLogs.filter(
+            (log) => log.level !== 'debug'
+        ).length
+
+        await producer.sendAll(
+            serverLogs.map((log): KeyValue => [uuidV4(), JSON.stringify(log)])
+        )
+
+        const receivedLogs = []
+        for await (const record of stream) {
+            receivedLogs.push(JSON.parse(record.valueString()))
+            if (receivedLogs.length >= expectedCount) {
+                break
+            }
+        }
+
+        expect(
+            receivedLogs.find((log) => log.level === 'debug')
+        ).toBeUndefined()
+
+        await expect(
+            consumer.streamWithConfig(Offset.FromBeginning(), {
+                smartmoduleType: SmartModuleType.Filter,
+                smartmoduleData: Buffer.from('not a module'),
+            })
+        ).rejects.toThrow(
+            'SmartModule is neither a WASM module nor a Gzipped WASM module'
+        )
+    })
+
+    test('Applies a SmartModule before records are written using `topicProducerWithConfig`', async () => {
+        const producer = await fluvio.topicProducerWithConfig(topic, {
+            smartmoduleType: SmartModuleType.Filter,
+            smartmoduleFile: './fixtures/server_logs_filter.wasm',
+        })
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const serverLogsFile = await fs.promises.readFile(
+            './fixtures/server_log.json',
+            'utf8'
+        )
+        const serverLogs: { message: string; level: string }[] =
+            JSON.parse(serverLogsFile)
+        const expectedCount = serverLogs.filter(
+            (log) => log.level !== 'debug'
+        ).length
+
+        await producer.sendAll(
+            serverLogs.map((log): KeyValue => [uuidV4(), JSON.stringify(log)])
+        )
+        await producer.flush()
+
+        // Records written without a SmartModule show the filter ran on produce
+        const stream = await consumer.createStream(Offset.FromBeginning())
+        const receivedLogs = []
+        for await (const record of stream) {
+            receivedLogs.push(JSON.parse(record.valueString()))
+            if (receivedLogs.length >= expectedCount) {
+                break
+            }
+        }
+
+        expect(
+            receivedLogs.find((log) => log.level === 'debug')
+        ).toBeUndefined()
+    })
+
+    test('Passes params to a SmartModule using `smartmoduleParams`', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const serverLogsFile = await fs.promises.readFile(
+            './fixtures/server_log.json',
+            'utf8'
+        )
+        const serverLogs: { message: string; level: string }[] =
+            JSON.parse(serverLogsFile)
+
+        await producer.sendAll(
+            serverLogs.map((log): KeyValue => [uuidV4(), JSON.stringify(log)])
+        )
+
+        // `level_filter.wasm` drops the records of the level given as param
+        for (const level of ['debug', 'info']) {
+            const stream = await consumer.streamWithConfig(
+                Offset.FromBeginning(),
+                {
+                    smartmoduleType: SmartModuleType.Filter,
+                    smartmoduleFile: './fixtures/level_filter.wasm',
+                    smartmoduleParams: { level },
+                }
+            )
+            const expectedLogs = serverLogs.filter((log) => log.level !== level)
+
+            const receivedLogs = []
+            for await (const record of stream) {
+                receivedLogs.push(JSON.parse(record.valueString()))
+                if (receivedLogs.length >= expectedLogs.length) {
+                    break
+                }
+            }
+
+            expect(receivedLogs).toEqual(expectedLogs)
+        }
+    })
+
+    test('Applies a chain of SmartModules using `smartmodules`', async () => {
+        const producer = await fluvio.topicProducer(topic)
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const serverLogsFile = await fs.promises.readFile(
+            './fixtures/server_log.json',
+            'utf8'
+        )
+        const serverLogs: { message: string; level: string }[] =
+            JSON.parse(serverLogsFile)
+        const wasmSmartModule = await fs.promises.readFile(
+            './fixtures/server_logs_filter.wasm.gz'
+        )
+        const stream = await consumer.streamWithConfig(Offset.FromBeginning(), {
+            smartmodules: [
+                {
+                    type: SmartModuleType.Filter,
+                    file: './fixtures/server_logs_filter.wasm',
+                },
+                {
+                    type: SmartModuleType.Filter,
+                    data: wasmSmartModule.toString('base64'),
+                    file: undefined,
+                    params: undefined,
+                },
+            ],
+        })
+        const expectedCount = serverLogs.filter(
+            (log) => log.level !== 'debug'
+        ).length
+
+        await producer.sendAll(
+            serverLogs.map((log): KeyValue => [uuidV4(), JSON.stringify(log)])
+        )
+
+        const receivedLogs = []
+        for await (const record of stream) {
+            receivedLogs.push(JSON.parse(record.valueString()))
+            if (receivedLogs.length >= expectedCount) {
+                break
+            }
+        }
+
+        expect(
+            receivedLogs.find((log) => log.level === 'debug')
+        ).toBeUndefined()
+
+        await expect(
+            consumer.streamWithConfig(Offset.FromBeginning(), {
+                smartmoduleType: SmartModuleType.Filter,
+                smartmoduleFile: './fixtures/server_logs_filter.wasm',
+                smartmodules: [
+                    {
+                        type: SmartModuleType.Filter,
+                        data: wasmSmartModule.toString('base64'),
+                    },
+                ],
+            })
+        ).rejects.toThrow('smartmodules can not be combined')
+    })
+
+    test('Only accepts an accumulator for aggregate SmartModules', async () => {
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+
+        await expect(
+            consumer.streamWithConfig(Offset.FromBeginning(), {
+                smartmoduleType: SmartModuleType.Filter,
+                smartmoduleFile: './fixtures/server_logs_filter.wasm',
+                smartmoduleAccumulator: Buffer.from('0'),
+            })
+        ).rejects.toThrow(
+            'smartmoduleAccumulator can only be used with the "aggregate" SmartModule type'
+        )
+    })
+
+    test('Looks back at earlier records with `smartmoduleLookback`', async () => {
+        const keyless = (value: string): KeyValue => [null, value]
+        const producer = await fluvio.topicProducer(topic)
+        await producer.sendAll(['5', '2', '4'].map(keyless))
+        await producer.flush()
+
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+        const stream = await consumer.streamWithConfig(Offset.FromEnd(), {
+            smartmoduleType: SmartModuleType.Filter,
+            smartmoduleFile: './fixtures/max_value_filter.wasm',
+            smartmoduleLookback: { last: 3 },
+        })
+        // 3 is below the 5 looked back at, so only 6 passes the filter
+        await producer.sendAll(['3', '6'].map(keyless))
+
+        for await (const record of stream) {
+            expect(record.valueString()).toEqual('6')
+            break
+        }
+    })
+
+    test('Requires a count or age for a SmartModule lookback', async () => {
+        const consumer = await fluvio.partitionConsumer(topic, 0)
+
+        await expect(
+            consumer.streamWithConfig(Offset.FromBeginning(), {
+                smartmodules: [
+                    {
+                        type: SmartModuleType.Filter,
+                        file: './fixtures/server_logs_filter.wasm',
+                        lookback: {},
+                    },
+                ],
+            })
+        ).rejects.toThrow('lookback must provide last, ageMs or both')
+    })
+
+    test('Requires a SmartModule for SmartModule options', async () => {
+        await expect(
+            fluvio.topicProducerWithConfig(topic, {
+                smartmoduleParams: { key: 'value' },
+            })
+        ).rejects.toThrow(
+            'smartmoduleParams requires one of smartmoduleFile, smartmoduleName or smartmoduleData'
+        )
+        await expect(
+            fluvio.topicProducerWithConfig(topic, {
+                smartmoduleAccumulator: '0',
+            })
+        ).rejects.toThrow('smartmoduleAccumulator requires one of')
+    })
+
+    test('Ignores SmartModule options left undefined', async () => {
+        await expect(
+            fluvio.topicProducerWithConfig(topic, {
+                smartmoduleParams: undefined,
+                smartmoduleAccumulator: undefined,
+            })
+        ).resolves.toBeDefined()
+    })
+
     test('Complains when providing two SmartModule options at the same time', async () => {
         const consumer = await fluvio.partitionConsumer(topic, 0)
         const wasmSmartModule = await fs.promises.readFile(
@@ -245,12 +1103,57 @@ describe('Configures a SmartModule', () => {
     })
 })
 
+describe('Tests a SmartModule locally', () => {
+    test('Filters records without a cluster', async () => {
+        const serverLog = await fs.promises.readFile(
+            './fixtures/server.log',
+            'utf8'
+        )
+        const lines = serverLog.split('\n').filter((line) => line.length > 0)
+
+        const output = await testSmartModule(
+            {
+                smartmoduleType: SmartModuleType.Filter,
+                smartmoduleFile: './fixtures/server_logs_filter.wasm',
+            },
+            lines.map((line): KeyValue => [null, line])
+        )
+
+        expect(output.map((record) => record.value.toString())).toEqual(
+            lines.filter((line) => JSON.parse(line).level !== 'debug')
+        )
+        expect(output.every((record) => record.key === null)).toBe(true)
+    })
+
+    test('Rejects SmartModules stored on the cluster', async () => {
+        await expect(
+            testSmartModule(
+                {
+                    smartmoduleType: SmartModuleType.Filter,
+                    smartmoduleName: 'server-logs-filter',
+                },
+                [['key', 'value']]
+            )
+        ).rejects.toMatchObject({ kind: 'smartmodule' })
+    })
+})
+
+describe('FluvioError', () => {
+    test('Keeps numeric error codes and names them with `codeName`', () => {
+        expect(ErrorCode.UnknownServerError).toBe(0)
+        expect(ErrorCode.TopicNotFound).toBe(11)
+        expect(ErrorCode.PartitionNotLeader).toBe(16)
+        // `codeName` is the reverse mapping of `code`
+        expect(ErrorCode[ErrorCode.TopicNotFound]).toBe('TopicNotFound')
+    })
+})
+
 describe('MacOSCi', () => {
     test('', async () => {
-        // The errors will either be ['Fluvio socket error'] or ['Fluvio config error']
-        // Getting expect to work with either is annoying.
-        let error = await expect(Fluvio.connect()).rejects.toEqual([
-            'Config error: Config has no active profile',
-        ])
+        await expect(Fluvio.connect()).rejects.toMatchObject({
+            message: 'Config error: Config has no active profile',
+            kind: 'config',
+            retriable: false,
+        })
     })
 })
@@ -1,5 +1,6 @@
 /* tslint:disable:max-classes-per-file */
 import { EventEmitter } from 'events'
+import { Readable, Writable } from 'stream'
 
 export const DEFAULT_HOST = '127.0.0.1'
 export const DEFAULT_PORT = 9003
@@ -14,6 +15,7 @@ export const DEFAULT_IGNORE_RACK_ASSIGNMENT = false
 export const DEFAULT_MIN_ID = 0
 export const DEFAULT_OFFSET = 0
 export const DEFAULT_OFFSET_FROM = 'end'
+export const DEFAULT_BATCH_QUEUE_SIZE = 100
 
 // Set the path to the native module
 // to be used for the client; Set `FLUVIO_DEV`
@@ -87,24 +89,134 @@ export interface TopicProducerConfig {
      */
     batchSize?: number;
     /**
-     * Maximum time in milliseconds to wait before sending a batch, even if not full
+     * Maximum number of batches waiting to be sent
+     */
+    batchQueueSize?: number;
+    /**
+     * Maximum time in milliseconds to wait for the SPU to acknowledge
+     * a produce request before it fails. This is not the time batches
+     * wait to fill up, which is set with `lingerMs`. Must not be negative.
      */
     timeoutMs?: number;
     /**
-     * Time in milliseconds to wait for additional records before sending a batch
+     * Time in milliseconds to wait for additional records before sending
+     * a batch. Must not be negative.
      */
     lingerMs?: number;
     /**
      * Compression algorithm to use for batches
-     * Supported values: "none", "gzip", "snappy", "lz4"
+     * Supported values: "none", "gzip", "snappy", "lz4", "zstd"
      */
-    compression?: "none" | "gzip" | "snappy" | "lz4";
+    compression?: "none" | "gzip" | "snappy" | "lz4" | "zstd";
     /**
      * Maximum size of a single request in bytes
      */
     maxRequestSize?: number;
+    /**
+     * Chooses the partition of records sent without an explicit partition.
+     * Defaults to hashing the key, and round-robin for records without one.
+     */
+    partitioner?: Partitioner;
+    /**
+     * `at-most-once` sends each batch once and does not report lost records.
+     * `at-least-once` retries failed batches following `retryPolicy`.
+     * Defaults to `at-least-once` when a `retryPolicy` is given.
+     */
+    deliverySemantic?: DeliverySemantic;
+    /**
+     * How failed batches are retried with `at-least-once` delivery
+     */
+    retryPolicy?: RetryPolicy;
+    /**
+     * `read-committed` waits for records to be replicated before acknowledging them,
+     * `read-uncommitted` only waits for the leader
+     */
+    isolation?: Isolation;
+    /**
+     * Type of the SmartModule applied to records before they are written to the topic,
+     * required when one of `smartmoduleFile`, `smartmoduleName` or `smartmoduleData` is given
+     */
+    smartmoduleType?: SmartModuleType;
+    /**
+     * Path to a SmartModule WASM file, see `ConsumerConfig.smartmoduleFile`
+     */
+    smartmoduleFile?: string;
+    /**
+     * SmartModule WASM module, see `ConsumerConfig.smartmoduleData`
+     */
+    smartmoduleData?: string | ArrayBuffer | ArrayBufferView;
+    /**
+     * Name of a SmartModule stored on the cluster
+     */
+    smartmoduleName?: string;
+    /**
+     * Parameters passed to the SmartModule, see `ConsumerConfig.smartmoduleParams`
+     */
+    smartmoduleParams?: SmartModuleParams;
+    /**
+     * Initial accumulator of an `aggregate` SmartModule, see `ConsumerConfig.smartmoduleAccumulator`
+     */
+    smartmoduleAccumulator?: string | ArrayBuffer | ArrayBufferView;
+    /**
+     * SmartModules applied in order, instead of a single `smartmoduleType`
+     */
+    smartmodules?: SmartModuleOptions[];
+}
+
+export type DeliverySemantic = 'at-most-once' | 'at-least-once'
+
+export type Isolation = 'read-committed' | 'read-uncommitted'
+
+/**
+ * Settings for retrying failed batches. Missing settings keep the client defaults.
+ * Durations must not be negative.
+ */
+export interface RetryPolicy {
+    /**
+     * Number of times a batch is retried, a non-negative integer
+     */
+    maxRetries?: number;
+    /**
+     * Delay in milliseconds before the first retry
+     */
+    initialDelayMs?: number;
+    /**
+     * Upper bound in milliseconds of the delay between retries
+     */
+    maxDelayMs?: number;
+    /**
+     * Time in milliseconds after which a batch is no longer retried
+     */
+    timeoutMs?: number;
+    /**
+     * How the delay grows between retries
+     */
+    strategy?: 'fixed' | 'exponential' | 'fibonacci';
 }
 
+/**
+ * Built-in partitioning strategies
+ *
+ * - `round-robin`: each record goes to the next partition, keys are ignored
+ * - `key-hash`: Fluvio's default partitioner, records with the same key go
+ *   to the same partition and records without a key are sent round-robin
+ * - `sticky`: like `key-hash`, but runs of records without a key
+ *   are sent to the same partition to fill batches
+ */
+export type PartitionerStrategy = 'round-robin' | 'key-hash' | 'sticky'
+
+/**
+ * Returns the partition a record should be sent to, an integer between 0 and
+ * `partitionCount - 1`. Other values make the send fail.
+ */
+export type PartitionerFunction = (
+    key: ProducerItem | null,
+    value: ProducerItem,
+    partitionCount: number
+) => number
+
+export type Partitioner = PartitionerStrategy | PartitionerFunction
+
 /**
  * Provides access to the data within a Record that was consumed
  */
@@ -133,22 +245,97 @@ export interface Record {
      * Returns the Value of this Record as a string
      */
     valueString(): string
+
+    /**
+     * Returns the offset of this Record within its partition
+     */
+    offset(): bigint
+
+    /**
+     * Returns the partition this Record was consumed from
+     */
+    partition(): number
+
+    /**
+     * Returns the timestamp of this Record in milliseconds since the Unix epoch,
+     * or -1 if the Record has no timestamp
+     */
+    timestamp(): bigint
+
+    /**
+     * Returns the topic this Record was consumed from, or null if it is unknown
+     */
+    topic(): string | null
 }
 
+/**
+ * Callbacks notified about the lifecycle of a `PartitionConsumer.stream`
+ */
+export interface StreamHandlers {
+    /**
+     * Called whenever the running stream reports an error
+     */
+    onError?: (error: FluvioError) => void
+
+    /**
+     * Called once the stream has stopped, either because it ended
+     * or because `endStream` was called
+     */
+    onEnd?: () => void
+}
+
+/**
+ * Event emitted by the native stream callback
+ */
+type StreamEvent =
+    | { type: 'record'; record: Record }
+    | { type: 'error'; error: FluvioError }
+    | { type: 'end' }
+
 /**
  * An item that may be sent via the Producer is a string or byte buffer.
+ * Views such as a Node `Buffer`, a `Uint8Array` or a `DataView` send
+ * only the bytes they cover.
  */
-type ProducerItem = string | ArrayBuffer
+export type ProducerItem = string | ArrayBuffer | ArrayBufferView
 
 /**
  * A key/value element that may be sent via the Producer.
+ * A `null` or `undefined` key sends a record without a key.
  */
-export type KeyValue = [ProducerItem, ProducerItem]
+export type KeyValue = [ProducerItem | null | undefined, ProducerItem]
+
+/**
+ * Where a produced record was stored
+ */
+export interface ProduceMetadata {
+    partition: number
+    offset: bigint
+}
+
+/**
+ * A record queued by `TopicProducer.queueAll`
+ */
+export interface ProduceOutput {
+    /**
+     * Resolves with where the record was stored once the SPU has
+     * acknowledged it, which happens after the batch holding it is sent
+     * (see `TopicProducerConfig`)
+     */
+    wait(): Promise<ProduceMetadata>
+}
 
 export interface TopicProducer {
     sendRecord(data: string, partition: number): Promise<void>
-    send(key: ProducerItem, value: ProducerItem): Promise<void>
-    sendAll(records: KeyValue[]): Promise<void>
+    send(
+        key: ProducerItem | null | undefined,
+        value: ProducerItem,
+        partition?: number
+    ): Promise<ProduceMetadata>
+    sendAll(records: KeyValue[], partition?: number): Promise<ProduceMetadata[]>
+    queueAll(records: KeyValue[], partition?: number): Promise<ProduceOutput[]>
+    partitionCount(): Promise<number>
+    createWritable(options?: ProducerWritableOptions): ProducerWritable
     flush(): Promise<void>
 }
 
@@ -186,6 +373,8 @@ export interface TopicProducer {
  */
 export class TopicProducer {
     private inner: TopicProducer
+    private partitioner?: PartitionerFunction
+    private batchQueueSize: number
     /**
      * Private constructor
      *
@@ -195,8 +384,14 @@ export class TopicProducer {
      *
      * @param inner The native node module created by `await (new Fluvio().connect()).topicProducer()`
      */
-    private constructor(inner: TopicProducer) {
+    private constructor(
+        inner: TopicProducer,
+        partitioner?: PartitionerFunction,
+        batchQueueSize?: number
+    ) {
         this.inner = inner
+        this.partitioner = partitioner
+        this.batchQueueSize = batchQueueSize || DEFAULT_BATCH_QUEUE_SIZE
     }
 
     /**
@@ -204,13 +399,22 @@ export class TopicProducer {
      * `Fluvio` class. It is not meant to be called directly;
      *
      * @param inner The native node module created by `await (new Fluvio().connect()).topicProducer()`
+     * @param partitioner Partitioner function given in the producer config, if any
+     * @param batchQueueSize Batch queue size given in the producer config, if any
      */
-    public static create(inner: TopicProducer): TopicProducer {
-        return new TopicProducer(inner)
+    public static create(
+        inner: TopicProducer,
+        partitioner?: PartitionerFunction,
+        batchQueueSize?: number
+    ): TopicProducer {
+        return new TopicProducer(inner, partitioner, batchQueueSize)
     }
 
     /**
-     * Sends an event to a specific partition within this producer's topic
+     * Sends an event without a key to a specific partition within this producer's topic
+     *
+     * Fails if the topic has no such partition.
+     *
      * @param value Buffered data to send to the Fluvio partition
      * @param partition The partition that this record will be sent to
      */
@@ -225,33 +429,284 @@ export class TopicProducer {
 
     /**
      * Sends a key-value event to this producer's topic
-     * @param key The Key data of the record to send
+     *
+     * The returned promise resolves once the SPU has acknowledged the record,
+     * which happens after the batch holding it is sent (see `TopicProducerConfig`).
+     *
+     * @param key The Key data of the record to send, or `null` to send it without a key
      * @param value The Value data of the record to send
+     * @param partition The partition to send the record to, chosen from the key when omitted
      */
     async send(
-        key: string | ArrayBuffer,
-        value: string | ArrayBuffer
-    ): Promise<void> {
-        await this.inner.send(key, value)
+        key: ProducerItem | null | undefined,
+        value: ProducerItem,
+        partition?: number
+    ): Promise<ProduceMetadata> {
+        if (partition === undefined && this.partitioner) {
+            const partitionCount = await this.inner.partitionCount()
+            partition = checkPartition(
+                this.partitioner(key ?? null, value, partitionCount),
+                partitionCount
+            )
+        }
+        return await this.inner.send(key, value, partition)
     }
 
     /**
      * Sends a list of key-value elements to this producer's topic
      * @param elements
+     * @param partition The partition to send every element to, chosen from each key when omitted
+     * @returns The metadata of each element, in the same order, once all were acknowledged
      */
-    async sendAll(elements: KeyValue[]): Promise<void> {
-        await this.inner.sendAll(elements)
+    async sendAll(
+        elements: KeyValue[],
+        partition?: number
+    ): Promise<ProduceMetadata[]> {
+        return await this.byPartition(elements, partition, (records, target) =>
+            this.inner.sendAll(records, target)
+        )
+    }
+
+    /**
+     * Queues a list of key-value elements for this producer's topic
+     *
+     * Unlike `sendAll`, the returned promise resolves as soon as the producer
+     * has taken the elements, without waiting for them to be sent.
+     * Call `wait()` on an output to learn where its element was stored.
+     *
+     * ```typescript
+     * const outputs = await producer.queueAll(elements)
+     * await producer.flush()
+     * const metadata = await Promise.all(outputs.map((output) => output.wait()))
+     * ```
+     *
+     * @param elements
+     * @param partition The partition to send every element to, chosen from each key when omitted
+     * @returns The output of each element, in the same order
+     */
+    async queueAll(
+        elements: KeyValue[],
+        partition?: number
+    ): Promise<ProduceOutput[]> {
+        return await this.byPartition(elements, partition, (records, target) =>
+            this.inner.queueAll(records, target)
+        )
+    }
+
+    // Hands the elements of each partition to `send` together, then restores their order
+    private async byPartition<T>(
+        elements: KeyValue[],
+        partition: number | undefined,
+        send: (records: KeyValue[], partition?: number) => Promise<T[]>
+    ): Promise<T[]> {
+        const partitioner = this.partitioner
+        if (partition !== undefined || !partitioner) {
+            return await send(elements, partition)
+        }
+
+        const partitionCount = await this.inner.partitionCount()
+        const indexes = new Map<number, number[]>()
+        elements.forEach(([key, value], index) => {
+            const target = checkPartition(
+                partitioner(key ?? null, value, partitionCount),
+                partitionCount
+            )
+            const targetIndexes = indexes.get(target)
+            if (targetIndexes) {
+                targetIndexes.push(index)
+            } else {
+                indexes.set(target, [index])
+            }
+        })
+
+        const results: T[] = new Array(elements.length)
+        await Promise.all(
+            [...indexes].map(async ([target, targetIndexes]) => {
+                const sent = await send(
+                    targetIndexes.map((index) => elements[index]),
+                    target
+                )
+                targetIndexes.forEach((index, i) => (results[index] = sent[i]))
+            })
+        )
+        return results
+    }
+
+    /**
+     * Returns the number of partitions of this producer's topic
+     */
+    async partitionCount(): Promise<number> {
+        return await this.inner.partitionCount()
+    }
+
+    /**
+     * Returns a `Writable` that sends everything written to it with this producer
+     *
+     * ```typescript
+     * const writable = producer.createWritable()
+     * writable.write(['key', 'value'])
+     * writable.end()
+     * ```
+     */
+    createWritable(options?: ProducerWritableOptions): ProducerWritable {
+        return new ProducerWritable(this, {
+            maxPendingWrites: this.batchQueueSize,
+            ...options,
+        })
     }
     async flush(): Promise<void> {
         await this.inner.flush()
     }
 }
 
+export interface ProducerWritableOptions {
+    /**
+     * In object mode, which is the default, each chunk is either a `KeyValue`
+     * or a value sent without a key. Otherwise chunks are sent as values.
+     */
+    objectMode?: boolean
+    /**
+     * Number of chunks, or bytes outside of object mode, buffered before `write` returns false
+     */
+    highWaterMark?: number
+    /**
+     * Sends every record to this partition instead of using the partitioner
+     */
+    partition?: number
+    /**
+     * Number of writes awaiting acknowledgement before the stream stops accepting more,
+     * defaults to the producer's `batchQueueSize`
+     *
+     * A write sends the chunks buffered by the stream together, so it may hold
+     * more or fewer records than one of the producer's batches.
+     */
+    maxPendingWrites?: number
+}
+
+/**
+ * # Producer Writable
+ *
+ * A Node `Writable` sending the chunks written to it through a `TopicProducer`,
+ * created with `TopicProducer.createWritable`
+ *
+ * Chunks buffered by the stream are sent together with `queueAll`, one send
+ * after the other so records keep the order they were written in.
+ * Once `maxPendingWrites` writes await acknowledgement, more are held back
+ * and `write` returns false until the producer catches up.
+ * Ending the stream flushes the producer, and a failed send
+ * destroys the stream with the error.
+ *
+ * ```typescript
+ * import { pipeline } from 'stream/promises'
+ *
+ * await pipeline(fs.createReadStream('events.log'), split(), producer.createWritable())
+ * ```
+ */
+export class ProducerWritable extends Writable {
+    private producer: TopicProducer
+    private partition?: number
+    private maxPendingWrites: number
+    // Each send starts once the previous one has queued its records
+    private queued: Promise<void> = Promise.resolve()
+    // Sends whose records were not all acknowledged yet
+    private unacknowledged = new Set<Promise<unknown>>()
+    // Write callback held back until a send is acknowledged
+    private blocked?: (error?: Error | null) => void
+
+    constructor(
+        producer: TopicProducer,
+        options: ProducerWritableOptions = {}
+    ) {
+        super({
+            objectMode: options.objectMode ?? true,
+            highWaterMark: options.highWaterMark,
+        })
+        this.producer = producer
+        this.partition = options.partition
+        this.maxPendingWrites = Math.max(
+            1,
+            options.maxPendingWrites || DEFAULT_BATCH_QUEUE_SIZE
+        )
+    }
+
+    _write(
+        chunk: any,
+        encoding: BufferEncoding,
+        callback: (error?: Error | null) => void
+    ): void {
+        this._writev([{ chunk, encoding }], callback)
+    }
+
+    _writev(
+        chunks: { chunk: any; encoding: BufferEncoding }[],
+        callback: (error?: Error | null) => void
+    ): void {
+        const records = chunks.map(({ chunk }) => toKeyValue(chunk))
+        // Chunks still waiting when a send fails are dropped with the stream
+        const sent: Promise<ProduceOutput[]> = this.queued.then(() =>
+            this.destroyed
+                ? []
+                : this.producer.queueAll(records, this.partition)
+        )
+        this.queued = sent.then(
+            () => undefined,
+            () => undefined
+        )
+
+        const acknowledged = sent.then((outputs) =>
+            Promise.all(outputs.map((output) => output.wait()))
+        )
+        this.unacknowledged.add(acknowledged)
+        acknowledged
+            .catch((error) => this.destroy(error))
+            .then(() => {
+                this.unacknowledged.delete(acknowledged)
+                this.release()
+            })
+
+        if (this.unacknowledged.size < this.maxPendingWrites) {
+            callback()
+        } else {
+            this.blocked = callback
+        }
+    }
+
+    _final(callback: (error?: Error | null) => void): void {
+        this.queued
+            .then(() => this.producer.flush())
+            .then(() => Promise.all(this.unacknowledged))
+            .then(() => callback(), callback)
+    }
+
+    private release() {
+        const blocked = this.blocked
+        if (blocked && this.unacknowledged.size < this.maxPendingWrites) {
+            this.blocked = undefined
+            blocked()
+        }
+    }
+}
+
 export interface PartitionConsumer {
-    fetch(offset?: Offset): Promise<FetchablePartitionResponse>
-    stream(offset: Offset, cb: (record: Record) => void): Promise<void>
+    fetch(
+        offset?: Offset,
+        options?: FetchOptions
+    ): Promise<FetchablePartitionResponse>
+    stream(
+        offset: Offset,
+        cb: (record: Record) => void,
+        handlers?: StreamHandlers
+    ): Promise<void>
     endStream(): Promise<void>
     createStream(offset: Offset): Promise<AsyncIterable<Record>>
+    createBatchStream(
+        offset: Offset,
+        options?: BatchOptions
+    ): Promise<AsyncIterable<Record[]>>
+    createReadable(
+        offset: Offset,
+        options?: ConsumerReadableOptions
+    ): Promise<ConsumerReadable>
     streamWithConfig(
         offset: Offset,
         config: ConsumerConfig
@@ -321,33 +776,329 @@ export class PartitionConsumer {
      * It is a batch request for records from a particular offset in the partition.
      *
      * You specify the position of records to retrieve using an Offset,
-     * and receive the events as a list of records.
+     * and receive the events as a list of records. The fetch stops at the
+     * end of the partition, or earlier when one of the `options` limits is reached.
      *
      * @param {Offset} offset Describes the location of an event stored in a Fluvio partition
+     * @param {FetchOptions} options Optional limits on the size of the response
      */
-    async fetch(offset?: Offset): Promise<FetchablePartitionResponse> {
+    async fetch(
+        offset?: Offset,
+        options?: FetchOptions
+    ): Promise<FetchablePartitionResponse> {
         if (!offset) {
             offset = new Offset()
         }
-        return await this.inner.fetch(offset)
+        return await this.inner.fetch(offset, options || {})
+    }
+
+    /**
+     * Streams events from the given offset, invoking `cb` for each record
+     *
+     * The stream runs in the background until `endStream` is called.
+     * Failing to open the stream rejects the returned promise; errors raised
+     * while the stream is running are reported through `handlers.onError`,
+     * and `handlers.onEnd` is called once the stream has stopped.
+     *
+     * @param offset Describes the location of the first event to stream
+     * @param cb Callback invoked with each consumed record
+     * @param handlers Optional callbacks for stream errors and termination
+     */
+    async stream(
+        offset: Offset,
+        cb: (record: Record) => void,
+        handlers?: StreamHandlers
+    ): Promise<void> {
+        await this.inner.stream(offset, streamEventHandler(cb, handlers))
+        return
+    }
+
+    /**
+     * Stops every stream started with `stream` on this consumer
+     *
+     * Resolves once the background tasks have fully stopped and the
+     * underlying Fluvio streams are closed.
+     */
+    async endStream(): Promise<void> {
+        await this.inner.endStream()
+    }
+
+    /**
+     * This returns an [`AsyncIterable`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Symbol/asyncIterator).
+     * Usage:
+     * ```typescript
+     * const stream = await consumer.getIterator(Offset.FromBeginning())
+     * for await (const next of stream) {
+     *     console.log(next)
+     * }
+     * ```
+     */
+    async createStream(offset: Offset): Promise<AsyncIterable<Record>> {
+        let stream = await this.inner.createStream(offset)
+        stream[Symbol.asyncIterator] = () => {
+            return stream
+        }
+        return stream
+    }
+
+    /**
+     * Returns an `AsyncIterable` over arrays of records, pulling up to
+     * `maxRecords` records at once from the native stream.
+     *
+     * Prefer this over `createStream` for high throughput topics.
+     * Usage:
+     * ```typescript
+     * const batches = await consumer.createBatchStream(Offset.FromBeginning(), {
+     *     maxRecords: 500,
+     *     maxWaitMs: 100,
+     * })
+     * for await (const records of batches) {
+     *     console.log(records.length)
+     * }
+     * ```
+     */
+    async createBatchStream(
+        offset: Offset,
+        options?: BatchOptions
+    ): Promise<AsyncIterable<Record[]>> {
+        const stream = await this.inner.createStream(offset)
+        return batchIterable(stream, options)
+    }
+
+    /**
+     * Returns an object mode `Readable` of the records from the given offset
+     *
+     * Records are only fetched while the readable buffer has room,
+     * and destroying the readable closes the Fluvio stream.
+     * Usage:
+     * ```typescript
+     * const readable = await consumer.createReadable(Offset.FromBeginning())
+     * readable.pipe(transform).pipe(sink)
+     * ```
+     */
+    async createReadable(
+        offset: Offset,
+        options?: ConsumerReadableOptions
+    ): Promise<ConsumerReadable> {
+        const stream = await this.inner.createStream(offset)
+        return new ConsumerReadable(stream, options)
+    }
+
+    async streamWithConfig(
+        offset: Offset,
+        config: ConsumerConfig
+    ): Promise<AsyncIterable<Record>> {
+        let stream = await this.inner.streamWithConfig(
+            offset,
+            nativeSmartModuleConfig(config)
+        )
+        stream[Symbol.asyncIterator] = () => {
+            return stream
+        }
+        return stream
+    }
+}
+
+export interface ConsumerReadableOptions {
+    /**
+     * Number of records buffered before fetching pauses, defaults to 16
+     */
+    highWaterMark?: number
+}
+
+/**
+ * # Consumer Readable
+ *
+ * An object mode Node `Readable` of consumed records, created with
+ * `PartitionConsumer.createReadable`
+ *
+ * Records are pulled from the native stream only while the readable
+ * buffer is below `highWaterMark`, so slow destinations pause fetching.
+ * Errors consuming records destroy the readable, and destroying it
+ * closes the underlying Fluvio stream.
+ */
+export class ConsumerReadable extends Readable {
+    private iterator: any
+    private reading?: Promise<void>
+
+    constructor(iterator: any, options: ConsumerReadableOptions = {}) {
+        super({ objectMode: true, highWaterMark: options.highWaterMark })
+        this.iterator = iterator
+    }
+
+    _read(): void {
+        if (!this.reading) {
+            this.reading = this.pull().then(() => {
+                this.reading = undefined
+            })
+        }
+    }
+
+    _destroy(
+        error: Error | null,
+        callback: (error?: Error | null) => void
+    ): void {
+        // Closing resolves a pending read as done and drops the native stream
+        this.iterator.close().then(
+            () => callback(error),
+            () => callback(error)
+        )
+    }
+
+    // Pushes records until the buffer is full, the stream ends or fails
+    private async pull(): Promise<void> {
+        try {
+            for (;;) {
+                const next = await this.iterator.next()
+                if (this.destroyed) {
+                    return
+                }
+                if (next.done) {
+                    this.push(null)
+                    return
+                }
+                if (!this.push(next.value)) {
+                    return
+                }
+            }
+        } catch (error) {
+            this.destroy(error as Error)
+        }
     }
+}
+
+export interface TopicConsumer {
+    stream(
+        offset: Offset,
+        cb: (record: Record) => void,
+        handlers?: StreamHandlers
+    ): Promise<void>
+    endStream(): Promise<void>
+    createStream(offset: Offset): Promise<ConsumerStream>
+    createBatchStream(
+        offset: Offset,
+        options?: BatchOptions
+    ): Promise<AsyncIterable<Record[]>>
+    streamWithConfig(
+        offset: Offset,
+        config: ConsumerConfig
+    ): Promise<ConsumerStream>
+}
 
-    async stream(offset: Offset, cb: (record: Record) => void): Promise<void> {
-        await this.inner.stream(offset, cb)
+/**
+ * Records of a `TopicConsumer` stream, with control over the offsets
+ * stored on the cluster for the consumer id given in `ConsumerOffsetOptions`
+ */
+export interface ConsumerStream extends AsyncIterable<Record> {
+    /**
+     * Marks the last record returned by the stream as processed.
+     * Only needed with the `manual` offset strategy.
+     *
+     * Can be called while the stream is waiting for the next record.
+     */
+    commit(): Promise<void>
+    /**
+     * Sends the committed offset to the cluster
+     */
+    flush(): Promise<void>
+}
+
+/**
+ * # Topic Consumer
+ *
+ * ## Overview
+ *
+ * An interface for consuming events from several partitions of a topic at once
+ *
+ * Records from every selected partition are merged into a single stream;
+ * use `Record.partition()` to tell which partition a record came from.
+ * The offset given to `stream` or `createStream` is applied to each partition.
+ *
+ * ## Example Construction
+ *
+ * Do not call this constructor manually, instead use the method below:
+ *
+ * ```TypeScript
+ * const fluvio = await Fluvio.connect()
+ *
+ * // Consume every partition of the topic
+ * const consumer = await fluvio.topicConsumer("topic-name")
+ *
+ * // Or only a subset of them
+ * const subset = await fluvio.topicConsumer("topic-name", [0, 2])
+ *
+ * // Resume from the offsets stored on the cluster for "my-consumer"
+ * const stored = await fluvio.topicConsumer("topic-name", [], {
+ *     consumerId: "my-consumer",
+ * })
+ * ```
+ */
+export class TopicConsumer {
+    private inner: any // This is the native TopicConsumerJS
+
+    /**
+     * This method is not intended to be used directly. This is a helper
+     * method used by the `Fluvio` class to pass in a native object.
+     *
+     * @param inner The native node module created by `new Fluvio().connect().topicConsumer()`
+     */
+    private constructor(inner: any) {
+        this.inner = inner
+    }
+
+    public static create(inner: any) {
+        return new TopicConsumer(inner)
+    }
+
+    /**
+     * Streams events from the given offset, invoking `cb` for each record
+     *
+     * Behaves like `PartitionConsumer.stream`, across all selected partitions.
+     *
+     * @param offset Describes the location of the first event to stream in each partition
+     * @param cb Callback invoked with each consumed record
+     * @param handlers Optional callbacks for stream errors and termination
+     */
+    async stream(
+        offset: Offset,
+        cb: (record: Record) => void,
+        handlers?: StreamHandlers
+    ): Promise<void> {
+        await this.inner.stream(offset, streamEventHandler(cb, handlers))
         return
     }
 
     /**
-     * This returns an [`AsyncIterable`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Symbol/asyncIterator).
-     * Usage:
-     * ```typescript
-     * const stream = await consumer.getIterator(Offset.FromBeginning())
-     * for await (const next of stream) {
-     *     console.log(next)
-     * }
-     * ```
+     * Stops every stream started with `stream` on this consumer
+     */
+    async endStream(): Promise<void> {
+        await this.inner.endStream()
+    }
+
+    /**
+     * Marks the last record passed to the callback of each running `stream`
+     * as processed
+     *
+     * Requires a `consumerId` and the `manual` offset strategy.
+     */
+    async commit(): Promise<void> {
+        await this.inner.commit()
+    }
+
+    /**
+     * Sends the committed offsets of each running `stream` to the cluster
+     */
+    async flush(): Promise<void> {
+        await this.inner.flush()
+    }
+
+    /**
+     * Returns an `AsyncIterable` over the records of all selected partitions
+     *
+     * When the consumer was created with a `consumerId`, the stream starts from
+     * the stored offsets and `offset` is only used for partitions without one.
      */
-    async createStream(offset: Offset): Promise<AsyncIterable<Record>> {
+    async createStream(offset: Offset): Promise<ConsumerStream> {
         let stream = await this.inner.createStream(offset)
         stream[Symbol.asyncIterator] = () => {
             return stream
@@ -355,11 +1106,27 @@ export class PartitionConsumer {
         return stream
     }
 
+    /**
+     * Returns an `AsyncIterable` over arrays of records from all selected partitions
+     *
+     * See `PartitionConsumer.createBatchStream`.
+     */
+    async createBatchStream(
+        offset: Offset,
+        options?: BatchOptions
+    ): Promise<AsyncIterable<Record[]>> {
+        const stream = await this.inner.createStream(offset)
+        return batchIterable(stream, options)
+    }
+
     async streamWithConfig(
         offset: Offset,
         config: ConsumerConfig
-    ): Promise<AsyncIterable<Record>> {
-        let stream = await this.inner.streamWithConfig(offset, config)
+    ): Promise<ConsumerStream> {
+        let stream = await this.inner.streamWithConfig(
+            offset,
+            nativeSmartModuleConfig(config)
+        )
         stream[Symbol.asyncIterator] = () => {
             return stream
         }
@@ -382,6 +1149,10 @@ export interface FluvioAdmin {
     listTopic(): Promise<string>
     listPartitions(): Promise<string>
     findPartition(topic: string): Promise<PartitionSpecMetadata>
+    createSmartModule(name: string, source: SmartModuleSource): Promise<string>
+    listSmartModules(): Promise<SmartModuleInfo[]>
+    findSmartModule(name: string): Promise<SmartModuleInfo | undefined>
+    deleteSmartModule(name: string): Promise<string>
 }
 
 /**
@@ -547,6 +1318,53 @@ export class FluvioAdmin {
     async findPartition(topic: string): Promise<PartitionSpecMetadata> {
         return await this.inner.findPartition(topic)
     }
+
+    /**
+     * Registers a SmartModule on the cluster, so consumers and producers
+     * can refer to it with `smartmoduleName`
+     *
+     * ```typescript
+     * await admin.createSmartModule('server-logs-filter', {
+     *     file: './server_logs_filter.wasm',
+     * })
+     * ```
+     *
+     * @param name Name the SmartModule is registered with
+     * @param source WASM module of the SmartModule
+     */
+    async createSmartModule(
+        name: string,
+        source: SmartModuleSource
+    ): Promise<string> {
+        return await this.inner.createSmartModule(name, source)
+    }
+
+    /**
+     * List the SmartModules registered on the cluster
+     */
+    async listSmartModules(): Promise<SmartModuleInfo[]> {
+        const buffer = await this.inner.listSmartModules()
+        return JSON.parse(arrayBufferToString(buffer as any))
+    }
+
+    /**
+     * Find a registered SmartModule by name
+     *
+     * @param name Name of the SmartModule to find
+     */
+    async findSmartModule(name: string): Promise<SmartModuleInfo | undefined> {
+        const buffer = await this.inner.findSmartModule(name)
+        return JSON.parse(arrayBufferToString(buffer as any)) ?? undefined
+    }
+
+    /**
+     * Delete a registered SmartModule by name
+     *
+     * @param name Name of the SmartModule to delete
+     */
+    async deleteSmartModule(name: string): Promise<string> {
+        return await this.inner.deleteSmartModule(name)
+    }
 }
 
 export interface FluvioClient {
@@ -558,6 +1376,17 @@ export interface FluvioClient {
         topic: string,
         partition: number
     ): Promise<PartitionConsumer>
+    topicConsumer(
+        topic: string,
+        partitions?: number[],
+        offsets?: ConsumerOffsetOptions
+    ): Promise<TopicConsumer>
+    consumerOffsets(): Promise<ConsumerOffset[]>
+    deleteConsumerOffset(
+        consumerId: string,
+        topic: string,
+        partition: number
+    ): Promise<void>
     admin(): Promise<FluvioAdmin>
 }
 
@@ -717,11 +1546,22 @@ export default class Fluvio implements FluvioClient {
      */
     async topicProducerWithConfig(topic: string, config: TopicProducerConfig): Promise<TopicProducer> {
         this.checkConnection()
-        const inner = await this.client?.topicProducerWithConfig(topic, config)
+        // Partitioner functions run in JS, only built-in strategies are passed to the native client
+        const { partitioner, ...nativeConfig } = nativeSmartModuleConfig(config)
+        const inner = await this.client?.topicProducerWithConfig(
+            topic,
+            typeof partitioner === 'string'
+                ? { ...nativeConfig, partitioner }
+                : nativeConfig
+        )
         if (!inner) {
             throw new Error('Failed to create topic producer with config')
         }
-        return TopicProducer.create(inner)
+        return TopicProducer.create(
+            inner,
+            typeof partitioner === 'function' ? partitioner : undefined,
+            config.batchQueueSize
+        )
     }
 
     /**
@@ -748,6 +1588,55 @@ export default class Fluvio implements FluvioClient {
         return PartitionConsumer.create(inner)
     }
 
+    /**
+     *
+     * Creates a new TopicConsumer that reads several partitions of a topic as one stream
+     *
+     * @param topic topic string
+     * @param partitions partition ids to consume; all partitions of the topic when omitted
+     * @param offsets consumer id and strategy used to store offsets on the cluster
+     */
+    async topicConsumer(
+        topic: string,
+        partitions?: number[],
+        offsets?: ConsumerOffsetOptions
+    ): Promise<TopicConsumer> {
+        this.checkConnection()
+        const inner = await this.client?.topicConsumer(
+            topic,
+            partitions || [],
+            offsets || {}
+        )
+        if (!inner) {
+            throw new Error('Failed to create topic consumer')
+        }
+        return TopicConsumer.create(inner)
+    }
+
+    /**
+     * Lists the offsets stored on the cluster for every consumer id
+     */
+    async consumerOffsets(): Promise<ConsumerOffset[]> {
+        this.checkConnection()
+        const offsets = await this.client?.consumerOffsets()
+        if (!offsets) {
+            throw new Error('Failed to list consumer offsets')
+        }
+        return offsets
+    }
+
+    /**
+     * Deletes the offset stored for `consumerId` on a partition of `topic`
+     */
+    async deleteConsumerOffset(
+        consumerId: string,
+        topic: string,
+        partition: number
+    ): Promise<void> {
+        this.checkConnection()
+        await this.client?.deleteConsumerOffset(consumerId, topic, partition)
+    }
+
     /**
      * Provides an interface for managing a Fluvio cluster
      */
@@ -935,10 +1824,16 @@ export class TopicSpec {
 export enum OffsetFrom {
     Beginning = 'beginning',
     End = 'end',
+    Absolute = 'absolute',
 }
 
 export interface Offset {
-    index: number
+    /**
+     * Record index; relative to the beginning or end of the partition,
+     * or the exact offset of a record when `from` is `absolute`.
+     * Use a BigInt for offsets beyond `Number.MAX_SAFE_INTEGER`.
+     */
+    index: number | bigint
     from?: OffsetFrom | string
 }
 
@@ -954,6 +1849,14 @@ export class Offset {
     public static FromEnd(): Offset {
         return new Offset({ index: 0, from: OffsetFrom.End })
     }
+
+    /**
+     * Offset pointing at an exact record, e.g. one previously read from `Record.offset()`
+     * @param index Absolute offset of the record within the partition
+     */
+    public static Absolute(index: number | bigint): Offset {
+        return new Offset({ index, from: OffsetFrom.Absolute })
+    }
 }
 
 export enum SmartModuleType {
@@ -961,25 +1864,246 @@ export enum SmartModuleType {
     Map = 'map',
     ArrayMap = 'array_map',
     FilterMap = 'filter_map',
+    Aggregate = 'aggregate',
+}
+
+/**
+ * Parameters read by a SmartModule from its init function, values are passed as strings
+ */
+export type SmartModuleParams = { [key: string]: string | number | boolean }
+
+/**
+ * One SmartModule of a chain, from exactly one of `file`, `name` or `data`
+ */
+export interface SmartModuleOptions {
+    type: SmartModuleType
+    /**
+     * Path to a SmartModule WASM file, plain or Gzipped
+     */
+    file?: string
+    /**
+     * Name of a SmartModule stored on the cluster
+     */
+    name?: string
+    /**
+     * SmartModule WASM module, see `ConsumerConfig.smartmoduleData`
+     */
+    data?: string | ArrayBuffer | ArrayBufferView
+    params?: SmartModuleParams
+    /**
+     * Initial accumulator, only used by `aggregate` SmartModules
+     */
+    accumulator?: string | ArrayBuffer | ArrayBufferView
+    lookback?: SmartModuleLookback
+}
+
+/**
+ * Records read by a SmartModule's `look_back` function before it processes the stream
+ *
+ * Given both, at most `last` records newer than `ageMs` are read.
+ */
+export interface SmartModuleLookback {
+    /**
+     * Number of most recent records to read
+     */
+    last?: number
+    /**
+     * Only read records produced within this many milliseconds
+     */
+    ageMs?: number
 }
 
 export interface ConsumerConfig {
     maxBytes?: number
-    smartmoduleType: SmartModuleType
+    /**
+     * Required when one of `smartmoduleFile`, `smartmoduleName` or `smartmoduleData` is given
+     */
+    smartmoduleType?: SmartModuleType
     /**
      * Path to a SmartModule WASM file.
      *
      * @remarks
      * Internally replaces the value provided to `smartmoduleData`,
      * you must provide one, either `smartmoduleFile` or `smartmoduleData`.
+     * The file may hold a plain or a Gzipped WASM module.
      */
     smartmoduleFile?: string
 
     /**
-     * Gzipped and Base64 encoded SmartModule WASM file.
+     * SmartModule WASM module, given as a Base64 encoded string or as bytes,
+     * such as the `Buffer` returned by `fs.promises.readFile`.
+     *
+     * @remarks
+     * Plain and Gzipped modules are both accepted, told apart by their first bytes.
      */
-    smartmoduleData?: string
+    smartmoduleData?: string | ArrayBuffer | ArrayBufferView
     smartmoduleName?: string
+    /**
+     * Parameters passed to the SmartModule, so one WASM module can be reused
+     * with different settings
+     *
+     * ```typescript
+     * await consumer.streamWithConfig(Offset.FromBeginning(), {
+     *     smartmoduleType: SmartModuleType.Filter,
+     *     smartmoduleName: 'level-filter',
+     *     smartmoduleParams: { level: 'warn' },
+     * })
+     * ```
+     */
+    smartmoduleParams?: SmartModuleParams
+    /**
+     * Initial accumulator of an `aggregate` SmartModule, defaults to empty
+     *
+     * ```typescript
+     * await consumer.streamWithConfig(Offset.FromBeginning(), {
+     *     smartmoduleType: SmartModuleType.Aggregate,
+     *     smartmoduleName: 'running-total',
+     *     smartmoduleAccumulator: '0',
+     * })
+     * ```
+     */
+    smartmoduleAccumulator?: string | ArrayBuffer | ArrayBufferView
+    /**
+     * Records the SmartModule reads back to warm its state, such as a
+     * dedup filter rebuilding the keys it has already seen after a restart
+     *
+     * ```typescript
+     * await consumer.streamWithConfig(Offset.FromEnd(), {
+     *     smartmoduleType: SmartModuleType.Filter,
+     *     smartmoduleName: 'dedup',
+     *     smartmoduleLookback: { last: 1000, ageMs: 3600000 },
+     * })
+     * ```
+     */
+    smartmoduleLookback?: SmartModuleLookback
+    /**
+     * SmartModules applied in order, each to the output of the previous one
+     *
+     * ```typescript
+     * await consumer.streamWithConfig(Offset.FromBeginning(), {
+     *     smartmodules: [
+     *         { type: SmartModuleType.Filter, file: 'filter.wasm' },
+     *         { type: SmartModuleType.Map, name: 'uppercase' },
+     *     ],
+     * })
+     * ```
+     */
+    smartmodules?: SmartModuleOptions[]
+}
+
+/**
+ * SmartModules given to `testSmartModule`, from the same options as `ConsumerConfig`
+ *
+ * SmartModules stored on the cluster with `smartmoduleName` can not be tested locally,
+ * and lookbacks are not run.
+ */
+export type SmartModuleTestConfig = Omit<ConsumerConfig, 'maxBytes'>
+
+/**
+ * Record output by `testSmartModule`
+ */
+export interface SmartModuleTestRecord {
+    key: Buffer | null
+    value: Buffer
+}
+
+/**
+ * Runs SmartModules over the given records in this process, without a cluster or topic
+ *
+ * Resolves with the records output by the last SmartModule, or rejects with
+ * a `FluvioError` of kind `smartmodule` if a SmartModule fails to load or run.
+ *
+ * The WASM engine is only included when the native module is built with the
+ * `smartengine` feature (`node ./build.js --features smartengine`); otherwise
+ * this rejects with a `FluvioError` of kind `smartmodule` saying so.
+ *
+ * ```typescript
+ * const output = await testSmartModule(
+ *     {
+ *         smartmoduleType: SmartModuleType.Filter,
+ *         smartmoduleFile: './server_logs_filter.wasm',
+ *     },
+ *     lines.map((line): KeyValue => [null, line])
+ * )
+ * ```
+ */
+export async function testSmartModule(
+    config: SmartModuleTestConfig,
+    records: KeyValue[]
+): Promise<SmartModuleTestRecord[]> {
+    const output: { key?: ArrayBuffer; value: ArrayBuffer }[] =
+        await native.testSmartmodule(nativeSmartModuleConfig(config), records)
+    return output.map(({ key, value }) => ({
+        key: key ? Buffer.from(key) : null,
+        value: Buffer.from(value),
+    }))
+}
+
+/**
+ * How a `TopicConsumer` stores its offsets on the cluster
+ *
+ * - `auto`: offsets are committed as records are read and flushed periodically
+ * - `manual`: offsets are only committed by `ConsumerStream.commit()` and `flush()`
+ * - `none`: offsets are not stored
+ */
+export type OffsetManagementStrategy = 'auto' | 'manual' | 'none'
+
+export interface ConsumerOffsetOptions {
+    /**
+     * Identifies the consumer the offsets are stored for
+     */
+    consumerId?: string
+    /**
+     * Defaults to `auto` when a `consumerId` is given
+     */
+    offsetStrategy?: OffsetManagementStrategy
+    /**
+     * Non-negative interval in milliseconds between automatic flushes
+     */
+    offsetFlushMs?: number
+}
+
+/**
+ * Offset stored on the cluster for a consumer id and partition
+ */
+export interface ConsumerOffset {
+    consumerId: string
+    topic: string
+    partition: number
+    offset: bigint
+    /**
+     * Milliseconds since the Unix epoch
+     */
+    modifiedTime: bigint
+}
+
+/**
+ * Limits for each array yielded by `createBatchStream`
+ */
+export interface BatchOptions {
+    /**
+     * Maximum number of records in each batch, defaults to 100
+     */
+    maxRecords?: number
+    /**
+     * Maximum time in milliseconds to wait for a batch to fill up, defaults to 100
+     */
+    maxWaitMs?: number
+}
+
+/**
+ * Limits applied to a single `PartitionConsumer.fetch`
+ */
+export interface FetchOptions {
+    /**
+     * Maximum number of bytes requested from the SPU for each batch read,
+     * a positive integer
+     */
+    maxBytes?: number
+    /**
+     * Maximum number of records returned by the fetch, a positive integer
+     */
+    maxRecords?: number
 }
 
 export interface BatchHeader {
@@ -1011,6 +2135,10 @@ export interface RecordSet {
     batches: Batch[]
 }
 
+/**
+ * Error codes reported by the Fluvio cluster, set as `FluvioError.code`
+ * along with their name as `FluvioError.codeName`
+ */
 export enum ErrorCode {
     UnknownServerError,
     None,
@@ -1029,12 +2157,85 @@ export enum ErrorCode {
     TopicInvalidConfiguration,
     PartitionPendingInitialization,
     PartitionNotLeader,
+    SmartModuleNotFound,
+    SmartModuleInvalid,
+    SmartModuleInvalidExports,
+    SmartModuleRuntimeError,
+    SmartModuleChainInitError,
+    SmartModuleInitError,
+    SmartModuleLookBackError,
+    Other,
+}
+
+/**
+ * Where a `FluvioError` originated
+ *
+ * - `client`: invalid arguments or state detected by this client
+ * - `network`: the cluster could not be reached
+ * - `server`: the cluster rejected the request
+ * - `config`: the client configuration or profile is invalid
+ * - `smartmodule`: a SmartModule failed to load or run
+ */
+export type ErrorKind =
+    | 'client'
+    | 'network'
+    | 'server'
+    | 'config'
+    | 'smartmodule'
+
+/**
+ * Errors thrown by the native client
+ *
+ * ```typescript
+ * try {
+ *     await producer.send(key, value)
+ * } catch (error) {
+ *     if (isFluvioError(error) && error.retriable) {
+ *         // try again
+ *     }
+ * }
+ * ```
+ */
+export interface FluvioError extends Error {
+    /**
+     * Set when the cluster reported one of the codes listed in `ErrorCode`
+     */
+    code?: ErrorCode
+    /**
+     * Name of `code`, such as `'TopicNotFound'`
+     */
+    codeName?: keyof typeof ErrorCode
+    kind: ErrorKind
+    /**
+     * Whether the same request may succeed if retried
+     */
+    retriable: boolean
+    /**
+     * Messages of the errors that caused this one, outermost first
+     */
+    sources: string[]
+}
+
+export function isFluvioError(error: unknown): error is FluvioError {
+    return (
+        error instanceof Error &&
+        typeof (error as FluvioError).kind === 'string' &&
+        typeof (error as FluvioError).retriable === 'boolean'
+    )
 }
 
 export interface FetchablePartitionResponse {
     partitionIndex: number
     errorCode: ErrorCode
+    /**
+     * Offset following the last record committed to the partition
+     */
     highWatermark: number
+    /**
+     * Offset following the last record returned by the fetch,
+     * undefined if no records were returned
+     */
+    nextOffset?: number
     lastStableOffset: number
     logStartOffset: number
     records: RecordSet
@@ -1052,6 +2253,116 @@ export interface FetchablePartitionResponse {
 
 // utility methods
 
+function streamEventHandler(
+    cb: (record: Record) => void,
+    handlers?: StreamHandlers
+): (event: StreamEvent) => void {
+    return (event: StreamEvent) => {
+        switch (event.type) {
+            case 'record':
+                cb(event.record)
+                break
+            case 'error':
+                handlers?.onError?.(event.error)
+                break
+            case 'end':
+                handlers?.onEnd?.()
+                break
+        }
+    }
+}
+
+const DEFAULT_BATCH_MAX_RECORDS = 100
+const DEFAULT_BATCH_MAX_WAIT_MS = 100
+
+/**
+ * Copies an object without the properties set to `undefined`, which the
+ * native module would otherwise fail to convert
+ */
+function withoutUndefined(obj: object): any {
+    const copy: any = {}
+    for (const key of Object.keys(obj)) {
+        const value = (obj as any)[key]
+        if (value !== undefined) {
+            copy[key] = value
+        }
+    }
+    return copy
+}
+
+/**
+ * SmartModule params are passed to the native module as `[key, value]` entries
+ */
+function nativeSmartModuleConfig(
+    config: ConsumerConfig | TopicProducerConfig | SmartModuleTestConfig
+): any {
+    const entries = (params: SmartModuleParams) =>
+        Object.keys(params).map((key) => [key, String(params[key])])
+    const nativeConfig = withoutUndefined(config)
+    if (config.smartmoduleParams) {
+        nativeConfig.smartmoduleParams = entries(config.smartmoduleParams)
+    }
+    if ('smartmoduleLookback' in config && config.smartmoduleLookback) {
+        nativeConfig.smartmoduleLookback = withoutUndefined(
+            config.smartmoduleLookback
+        )
+    }
+    if (config.smartmodules) {
+        nativeConfig.smartmodules = config.smartmodules.map((smartmodule) => {
+            const nativeSmartModule = withoutUndefined(smartmodule)
+            if (smartmodule.params) {
+                nativeSmartModule.params = entries(smartmodule.params)
+            }
+            if (smartmodule.lookback) {
+                nativeSmartModule.lookback = withoutUndefined(
+                    smartmodule.lookback
+                )
+            }
+            return nativeSmartModule
+        })
+    }
+    return nativeConfig
+}
+
+function batchIterable(
+    stream: any,
+    options?: BatchOptions
+): AsyncIterable<Record[]> {
+    const maxRecords = options?.maxRecords ?? DEFAULT_BATCH_MAX_RECORDS
+    const maxWaitMs = options?.maxWaitMs ?? DEFAULT_BATCH_MAX_WAIT_MS
+    return {
+        [Symbol.asyncIterator]: () => ({
+            next: async (): Promise<IteratorResult<Record[]>> => {
+                // Batches that timed out empty are not worth yielding
+                for (;;) {
+                    const batch = await stream.nextBatch(maxRecords, maxWaitMs)
+                    if (batch.done || batch.value.length > 0) {
+                        return batch
+                    }
+                }
+            },
+        }),
+    }
+}
+
+function toKeyValue(chunk: any): KeyValue {
+    return Array.isArray(chunk) ? (chunk as KeyValue) : [null, chunk]
+}
+
+function checkPartition(partition: number, partitionCount: number): number {
+    if (
+        !Number.isInteger(partition) ||
+        partition < 0 ||
+        partition >= partitionCount
+    ) {
+        const max = partitionCount - 1
+        throw new RangeError(
+            `partitioner returned ${partition}, expected an integer 0 to ${max}`
+        )
+    }
+    return partition
+}
+
 function getRandomId(): number {
     // NOTE: Determine a better id than timestamp + random;
     return +(Math.random() * 1e4).toFixed(0) + 1
@@ -1070,6 +2381,58 @@ export function arrayBufferToString(data: ArrayBuffer): string {
     return Buffer.from(data).toString('utf8')
 }
 
+/**
+ * WASM module registered with `FluvioAdmin.createSmartModule`,
+ * from exactly one of `file` or `data`
+ */
+export interface SmartModuleSource {
+    /**
+     * Path to a SmartModule WASM file, plain or Gzipped
+     */
+    file?: string
+    /**
+     * SmartModule WASM module, see `ConsumerConfig.smartmoduleData`
+     */
+    data?: string | ArrayBuffer | ArrayBufferView
+    /**
+     * Path to the `SmartModule.toml` describing the package and its params
+     */
+    metadata?: string
+}
+
+/**
+ * Package and params of a SmartModule, as declared in its `SmartModule.toml`
+ */
+export interface SmartModuleMetadata {
+    package: {
+        name: string
+        group: string
+        version: string
+        apiVersion: string
+        description: string | null
+        license: string | null
+        visibility: 'private' | 'public'
+        repository: string | null
+    }
+    params: {
+        name: string
+        description: string | null
+        optional: boolean
+    }[]
+}
+
+export interface SmartModuleInfo {
+    name: string
+    /**
+     * Size of the stored, Gzipped WASM module in bytes
+     */
+    wasmSize: number
+    /**
+     * Set when the SmartModule was created with `metadata`
+     */
+    meta?: SmartModuleMetadata
+}
+
 export interface Topic {
     name: string
     spec: {
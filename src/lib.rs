@@ -4,12 +4,15 @@ mod consumer;
 mod producer;
 mod fluvio;
 mod error;
+mod smartmodule;
+mod smartengine;
 
 use shared::*;
 
 mod shared {
     pub const OFFSET_BEGINNING: &str = "beginning";
     pub const OFFSET_END: &str = "end";
+    pub const OFFSET_ABSOLUTE: &str = "absolute";
 
     pub const CLIENT_NOT_FOUND_ERROR_MSG: &str =
         "fluvio client not found; ensure fluvio client is instantiated correctly.";
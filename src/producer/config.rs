@@ -0,0 +1,276 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::debug;
+
+use fluvio::{Compression, Isolation, TopicProducerConfig, TopicProducerConfigBuilder};
+use fluvio::{DeliverySemantic, RetryPolicy, RetryStrategy};
+use fluvio::SmartModuleInvocation;
+
+use node_bindgen::core::{NjError, JSValue};
+use node_bindgen::core::val::JsEnv;
+use node_bindgen::core::val::JsObject;
+use node_bindgen::sys::napi_value;
+
+use crate::optional_property;
+use crate::smartmodule::smartmodule_invocations;
+
+use super::partitioner::{PartitionerKind, TargetPartition, TargetPartitioner};
+
+const AT_MOST_ONCE: &str = "at-most-once";
+const AT_LEAST_ONCE: &str = "at-least-once";
+
+const READ_COMMITTED: &str = "read-committed";
+const READ_UNCOMMITTED: &str = "read-uncommitted";
+
+const FIXED_DELAY: &str = "fixed";
+const EXPONENTIAL_BACKOFF: &str = "exponential";
+const FIBONACCI_BACKOFF: &str = "fibonacci";
+
+// TopicProducerConfigBuilder is used to create a TopicProducerConfig
+//
+// TopicProducerConfigBuilder::default()
+//     .compression(Compression::Lz4)
+//     .max_request_size(1024 * 1024)
+//     .batch_size(1024)
+//     .batch_queue_size(1024)
+//     .linger(Duration::from_millis(100))
+//     .build()
+//     .unwrap();
+//
+// The settings are kept rather than the built config, so the partitioner
+// can be wrapped to route explicit partitions when the producer is created.
+
+#[derive(Default)]
+pub struct TopicProducerConfigWrapper {
+    compression: Option<Compression>,
+    max_request_size: Option<usize>,
+    batch_size: Option<usize>,
+    batch_queue_size: Option<usize>,
+    linger: Option<Duration>,
+    timeout: Option<Duration>,
+    partitioner: Option<PartitionerKind>,
+    delivery_semantic: Option<DeliverySemantic>,
+    isolation: Option<Isolation>,
+    smartmodules: Vec<SmartModuleInvocation>,
+}
+
+impl TopicProducerConfigWrapper {
+    fn builder(&self) -> TopicProducerConfigBuilder {
+        let mut builder = TopicProducerConfigBuilder::default();
+        if let Some(compression) = self.compression {
+            builder = builder.compression(compression);
+        }
+        if let Some(max_request_size) = self.max_request_size {
+            builder = builder.max_request_size(max_request_size);
+        }
+        if let Some(batch_size) = self.batch_size {
+            builder = builder.batch_size(batch_size);
+        }
+        if let Some(batch_queue_size) = self.batch_queue_size {
+            builder = builder.batch_queue_size(batch_queue_size);
+        }
+        if let Some(linger) = self.linger {
+            builder = builder.linger(linger);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(delivery_semantic) = self.delivery_semantic {
+            builder = builder.delivery_semantic(delivery_semantic);
+        }
+        if let Some(isolation) = self.isolation {
+            builder = builder.isolation(isolation);
+        }
+        if !self.smartmodules.is_empty() {
+            builder = builder.smartmodules(self.smartmodules.clone());
+        }
+        builder
+    }
+
+    /// Builds the config of a producer that routes records to `target` when it is set
+    pub(super) fn build(&self, target: TargetPartition) -> Result<TopicProducerConfig> {
+        let partitioner = TargetPartitioner::new(target, self.partitioner);
+        Ok(self.builder().partitioner(Box::new(partitioner)).build()?)
+    }
+}
+
+impl JSValue<'_> for TopicProducerConfigWrapper {
+    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        debug!("converting topic producer config from JS");
+        if let Ok(js_obj) = env.convert_to_rust::<JsObject>(js_value) {
+            let mut config = Self::default();
+
+            // Extract compression if provided
+            if let Some(compression_str) = optional_property!("compression", String, js_obj) {
+                let compression = match compression_str.as_str() {
+                    "none" => Compression::None,
+                    "gzip" => Compression::Gzip,
+                    "snappy" => Compression::Snappy,
+                    "lz4" => Compression::Lz4,
+                    "zstd" => Compression::Zstd,
+                    _ => {
+                        return Err(NjError::Other(format!(
+                            "Invalid compression type: {}",
+                            compression_str
+                        )))
+                    }
+                };
+                config.compression = Some(compression);
+            }
+
+            // Extract max_request_size if provided
+            if let Some(max_request_size) = optional_property!("maxRequestSize", i64, js_obj) {
+                config.max_request_size = Some(max_request_size as usize);
+            }
+
+            // Extract batch_size if provided
+            if let Some(batch_size) = optional_property!("batchSize", i64, js_obj) {
+                config.batch_size = Some(batch_size as usize);
+            }
+
+            // Extract batch_queue_size if provided
+            if let Some(batch_queue_size) = optional_property!("batchQueueSize", i64, js_obj) {
+                config.batch_queue_size = Some(batch_queue_size as usize);
+            }
+
+            // Extract linger if provided (in milliseconds),
+            // `linger` is still accepted from callers that predate `lingerMs`
+            config.linger = match duration_ms("lingerMs", &js_obj)? {
+                Some(linger) => Some(linger),
+                None => duration_ms("linger", &js_obj)?,
+            };
+
+            // Extract the produce request timeout if provided (in milliseconds)
+            config.timeout = duration_ms("timeoutMs", &js_obj)?;
+
+            // Extract a built-in partitioner if provided,
+            // partitioner functions are applied by the JS wrapper
+            if let Some(name) = optional_property!("partitioner", String, js_obj) {
+                let partitioner = PartitionerKind::from_name(&name).ok_or_else(|| {
+                    NjError::Other(format!(
+                        "Invalid partitioner: {}. Must be one of {:?}, {:?} or {:?}",
+                        name,
+                        PartitionerKind::ROUND_ROBIN,
+                        PartitionerKind::KEY_HASH,
+                        PartitionerKind::STICKY
+                    ))
+                })?;
+                config.partitioner = Some(partitioner);
+            }
+
+            config.delivery_semantic = delivery_semantic(&js_obj)?;
+
+            if let Some(isolation) = optional_property!("isolation", String, js_obj) {
+                config.isolation = Some(match isolation.as_str() {
+                    READ_COMMITTED => Isolation::ReadCommitted,
+                    READ_UNCOMMITTED => Isolation::ReadUncommitted,
+                    _ => {
+                        return Err(NjError::Other(format!(
+                            "Invalid isolation: {}. Must be one of {:?} or {:?}",
+                            isolation, READ_COMMITTED, READ_UNCOMMITTED
+                        )))
+                    }
+                });
+            }
+
+            // SmartModules transform records before they are sent
+            config.smartmodules = smartmodule_invocations(&js_obj)?;
+
+            // Build the config once so invalid settings are reported here
+            config.builder().build().map_err(|err| {
+                NjError::Other(format!("Failed to build TopicProducerConfig: {}", err))
+            })?;
+            Ok(config)
+        } else {
+            Err(NjError::Other("parameter must be a JSON object".to_owned()))
+        }
+    }
+}
+
+/// Reads `deliverySemantic` and `retryPolicy`, where a retry policy implies at-least-once
+fn delivery_semantic(js_obj: &JsObject) -> Result<Option<DeliverySemantic>, NjError> {
+    let semantic = optional_property!("deliverySemantic", String, js_obj);
+    let retry_policy = optional_property!("retryPolicy", JsObject, js_obj)
+        .map(|policy| retry_policy(&policy))
+        .transpose()?;
+
+    match (semantic.as_deref(), retry_policy) {
+        (None, None) => Ok(None),
+        (Some(AT_MOST_ONCE), None) => Ok(Some(DeliverySemantic::AtMostOnce)),
+        (Some(AT_MOST_ONCE), Some(_)) => Err(NjError::Other(format!(
+            "retryPolicy can only be used with {:?} delivery",
+            AT_LEAST_ONCE
+        ))),
+        (Some(AT_LEAST_ONCE) | None, policy) => Ok(Some(DeliverySemantic::AtLeastOnce(
+            policy.unwrap_or_default(),
+        ))),
+        (Some(semantic), _) => Err(NjError::Other(format!(
+            "Invalid delivery semantic: {}. Must be one of {:?} or {:?}",
+            semantic, AT_MOST_ONCE, AT_LEAST_ONCE
+        ))),
+    }
+}
+
+/// Reads a retry policy, keeping the client defaults for missing settings
+fn retry_policy(js_obj: &JsObject) -> Result<RetryPolicy, NjError> {
+    let mut policy = RetryPolicy::default();
+
+    if let Some(max_retries) = non_negative_integer("maxRetries", js_obj)? {
+        policy.max_retries = max_retries;
+    }
+    if let Some(initial_delay) = duration_ms("initialDelayMs", js_obj)? {
+        policy.initial_delay = initial_delay;
+    }
+    if let Some(max_delay) = duration_ms("maxDelayMs", js_obj)? {
+        policy.max_delay = max_delay;
+    }
+    if let Some(timeout) = duration_ms("timeoutMs", js_obj)? {
+        policy.timeout = timeout;
+    }
+    if let Some(strategy) = optional_property!("strategy", String, js_obj) {
+        policy.strategy = match strategy.as_str() {
+            FIXED_DELAY => RetryStrategy::FixedDelay,
+            EXPONENTIAL_BACKOFF => RetryStrategy::ExponentialBackoff,
+            FIBONACCI_BACKOFF => RetryStrategy::FibonacciBackoff,
+            _ => {
+                return Err(NjError::Other(format!(
+                    "Invalid retry strategy: {}. Must be one of {:?}, {:?} or {:?}",
+                    strategy, FIXED_DELAY, EXPONENTIAL_BACKOFF, FIBONACCI_BACKOFF
+                )))
+            }
+        };
+    }
+
+    Ok(policy)
+}
+
+/// Reads a duration given in milliseconds, which must be a non-negative number
+fn duration_ms(key: &str, js_obj: &JsObject) -> Result<Option<Duration>, NjError> {
+    let Some(ms) = optional_property!(key, f64, js_obj) else {
+        return Ok(None);
+    };
+    if !ms.is_finite() || ms < 0.0 {
+        return Err(NjError::Other(format!(
+            "{} must be a non-negative number of milliseconds, got {}",
+            key, ms
+        )));
+    }
+    Ok(Some(Duration::from_millis(ms as u64)))
+}
+
+/// Reads a count, which must be a non-negative integer
+fn non_negative_integer(key: &str, js_obj: &JsObject) -> Result<Option<usize>, NjError> {
+    let Some(value) = optional_property!(key, f64, js_obj) else {
+        return Ok(None);
+    };
+    if value.fract() != 0.0 || !(0.0..=u32::MAX as f64).contains(&value) {
+        return Err(NjError::Other(format!(
+            "{} must be a non-negative integer no greater than {}, got {}",
+            key,
+            u32::MAX,
+            value
+        )));
+    }
+    Ok(Some(value as usize))
+}
@@ -0,0 +1,497 @@
+mod config;
+mod partitioner;
+
+pub use self::config::TopicProducerConfigWrapper;
+
+use self::partitioner::TargetPartition;
+
+use crate::CLIENT_NOT_FOUND_ERROR_MSG;
+use crate::error::FluvioErrorJS;
+
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use tracing::debug;
+use futures_util::future::{BoxFuture, FutureExt, Shared, try_join_all};
+use futures_util::lock::{Mutex as AsyncMutex, MutexGuard as AsyncMutexGuard};
+
+use fluvio::{Fluvio, FluvioError, ProduceOutput, RecordKey, TopicProducerPool};
+use fluvio::metadata::topic::TopicSpec;
+
+use node_bindgen::derive::node_bindgen;
+use node_bindgen::core::{NjError, JSValue};
+use node_bindgen::core::val::JsEnv;
+use node_bindgen::core::val::JsObject;
+use node_bindgen::core::TryIntoJs;
+use node_bindgen::sys::{self, napi_value};
+use node_bindgen::core::napi_call_result;
+use node_bindgen::core::JSClass;
+use node_bindgen::core::buffer::JSArrayBuffer;
+use node_bindgen::core::bigint::BigInt;
+
+const PARTITION_KEY: &str = "partition";
+const OFFSET_KEY: &str = "offset";
+
+/// Producer for a topic. Records sent to an explicit partition go through the
+/// same producer, routed by its partitioner while the send holds `send_lock`.
+pub struct TopicProducer {
+    client: Arc<Fluvio>,
+    topic: String,
+    producer: TopicProducerPool,
+    target: TargetPartition,
+    send_lock: AsyncMutex<()>,
+    partition_count: Mutex<Option<u32>>,
+}
+
+impl TopicProducer {
+    pub async fn new(
+        client: Arc<Fluvio>,
+        topic: String,
+        config: TopicProducerConfigWrapper,
+    ) -> Result<Self> {
+        let target = TargetPartition::default();
+        let producer = client
+            .topic_producer_with_config(topic.clone(), config.build(target.clone())?)
+            .await?;
+
+        Ok(Self {
+            client,
+            topic,
+            producer,
+            target,
+            send_lock: AsyncMutex::new(()),
+            partition_count: Mutex::new(None),
+        })
+    }
+
+    /// Routes the records sent while the returned guard is held to `partition`,
+    /// or to where the partitioner puts them for `None`
+    async fn route(&self, partition: Option<u32>) -> Result<AsyncMutexGuard<'_, ()>> {
+        if let Some(partition) = partition {
+            self.check_partition(partition).await?;
+            debug!("Sending to partition {} of {}", partition, self.topic);
+        }
+
+        let guard = self.send_lock.lock().await;
+        *self.target.lock().unwrap() = partition;
+        Ok(guard)
+    }
+
+    async fn partition_count(&self) -> Result<u32> {
+        let cached = *self.partition_count.lock().unwrap();
+        match cached {
+            Some(count) => Ok(count),
+            None => self.refresh_partition_count().await,
+        }
+    }
+
+    async fn refresh_partition_count(&self) -> Result<u32> {
+        let count = self.fetch_partition_count().await?;
+        self.partition_count.lock().unwrap().replace(count);
+        Ok(count)
+    }
+
+    async fn check_partition(&self, partition: u32) -> Result<()> {
+        let mut count = self.partition_count().await?;
+        // The topic may have gained partitions since the count was cached
+        if partition >= count {
+            count = self.refresh_partition_count().await?;
+        }
+
+        if partition >= count {
+            return Err(FluvioError::PartitionNotFound(self.topic.clone(), partition).into());
+        }
+        Ok(())
+    }
+
+    async fn fetch_partition_count(&self) -> Result<u32> {
+        let admin = self.client.admin().await;
+        let topics = admin.list::<TopicSpec, _>(vec![self.topic.clone()]).await?;
+        let topic = topics
+            .into_iter()
+            .find(|topic| topic.name == self.topic)
+            .ok_or_else(|| FluvioError::TopicNotFound(self.topic.clone()))?;
+        Ok(topic.spec.partitions())
+    }
+
+    /// Queues `records` for `partition`, returning once the producer has taken them
+    async fn queue_all(
+        &self,
+        records: Vec<(RecordKey, &[u8])>,
+        partition: Option<u32>,
+    ) -> Result<Vec<ProduceOutput>> {
+        let _route = self.route(partition).await?;
+        Ok(self.producer.send_all(records).await?)
+    }
+
+    async fn flush(&self) -> Result<()> {
+        self.producer.flush().await?;
+        Ok(())
+    }
+}
+
+impl TryIntoJs for TopicProducerJS {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        debug!("converting FluvioWrapper to js");
+        let new_instance = TopicProducerJS::new_instance(js_env, vec![])?;
+        debug!("instance created");
+        if let Some(inner) = self.inner {
+            TopicProducerJS::unwrap_mut(js_env, new_instance)?.set_client(inner);
+        }
+        Ok(new_instance)
+    }
+}
+
+pub struct TopicProducerJS {
+    inner: Option<TopicProducer>,
+}
+
+impl From<TopicProducer> for TopicProducerJS {
+    fn from(inner: TopicProducer) -> Self {
+        Self { inner: Some(inner) }
+    }
+}
+
+#[node_bindgen]
+impl TopicProducerJS {
+    #[node_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self { inner: None }
+    }
+
+    pub fn set_client(&mut self, client: TopicProducer) {
+        self.inner.replace(client);
+    }
+
+    fn client(&self) -> Result<&TopicProducer, FluvioErrorJS> {
+        self.inner
+            .as_ref()
+            .ok_or_else(|| FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))
+    }
+
+    #[node_bindgen]
+    async fn send_record(&self, value: String, partition: u32) -> Result<(), FluvioErrorJS> {
+        debug!("Sending record: {} to partition: {}", value, partition);
+        let client = self.client()?;
+        let _route = client.route(Some(partition)).await?;
+        client
+            .producer
+            .send(RecordKey::NULL, value.into_bytes())
+            .await?;
+        Ok(())
+    }
+
+    /// Resolves with where the record was stored once the SPU has acknowledged it
+    #[node_bindgen]
+    async fn send(
+        &self,
+        key: Nullable<ProduceArg>,
+        value: ProduceArg,
+        partition: Nullable<u32>,
+    ) -> Result<ProduceMetadataJS, FluvioErrorJS> {
+        let client = self.client()?;
+        let route = client.route(partition.into_inner()).await?;
+        let output = client
+            .producer
+            .send(record_key(key.as_ref()), value.as_bytes())
+            .await?;
+        // Other sends may go on while this one waits to be acknowledged
+        drop(route);
+        ProduceMetadataJS::wait(output).await
+    }
+
+    /// Resolves with the metadata of each record, in order, once all were acknowledged
+    #[node_bindgen]
+    async fn send_all(
+        &self,
+        elements: Vec<(Nullable<ProduceArg>, ProduceArg)>,
+        partition: Nullable<u32>,
+    ) -> Result<Vec<ProduceMetadataJS>, FluvioErrorJS> {
+        let outputs = self
+            .client()?
+            .queue_all(records(&elements), partition.into_inner())
+            .await?;
+        try_join_all(outputs.into_iter().map(ProduceMetadataJS::wait)).await
+    }
+
+    /// Resolves with the output of each record, in order, once all are queued
+    #[node_bindgen]
+    async fn queue_all(
+        &self,
+        elements: Vec<(Nullable<ProduceArg>, ProduceArg)>,
+        partition: Nullable<u32>,
+    ) -> Result<Vec<ProduceOutputJS>, FluvioErrorJS> {
+        let outputs = self
+            .client()?
+            .queue_all(records(&elements), partition.into_inner())
+            .await?;
+        Ok(outputs.into_iter().map(ProduceOutputJS::from).collect())
+    }
+
+    /// Number of partitions of the topic, used by partitioners implemented in JS
+    #[node_bindgen]
+    async fn partition_count(&self) -> Result<u32, FluvioErrorJS> {
+        Ok(self.client()?.partition_count().await?)
+    }
+
+    #[node_bindgen]
+    async fn flush(&self) -> Result<(), FluvioErrorJS> {
+        self.client()?.flush().await?;
+        Ok(())
+    }
+}
+
+type MetadataFuture = Shared<BoxFuture<'static, Result<ProduceMetadataJS, FluvioErrorJS>>>;
+
+impl TryIntoJs for ProduceOutputJS {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        let new_instance = ProduceOutputJS::new_instance(js_env, vec![])?;
+        if let Some(metadata) = self.metadata {
+            ProduceOutputJS::unwrap_mut(js_env, new_instance)?.set_metadata(metadata);
+        }
+        Ok(new_instance)
+    }
+}
+
+/// Record queued by `queue_all`
+pub struct ProduceOutputJS {
+    // Shared so `wait` can be called any number of times
+    metadata: Option<MetadataFuture>,
+}
+
+impl From<ProduceOutput> for ProduceOutputJS {
+    fn from(output: ProduceOutput) -> Self {
+        Self {
+            metadata: Some(ProduceMetadataJS::wait(output).boxed().shared()),
+        }
+    }
+}
+
+#[node_bindgen]
+impl ProduceOutputJS {
+    #[node_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self { metadata: None }
+    }
+
+    fn set_metadata(&mut self, metadata: MetadataFuture) {
+        self.metadata.replace(metadata);
+    }
+
+    /// Resolves with where the record was stored once the SPU has acknowledged it
+    #[node_bindgen]
+    async fn wait(&self) -> Result<ProduceMetadataJS, FluvioErrorJS> {
+        let metadata = self
+            .metadata
+            .clone()
+            .ok_or_else(|| FluvioErrorJS::new(CLIENT_NOT_FOUND_ERROR_MSG.to_owned()))?;
+        metadata.await
+    }
+}
+
+/// Where a produced record was stored
+#[derive(Clone)]
+pub struct ProduceMetadataJS {
+    partition: u32,
+    offset: i64,
+}
+
+impl ProduceMetadataJS {
+    async fn wait(output: ProduceOutput) -> Result<Self, FluvioErrorJS> {
+        let metadata = output.wait().await.map_err(anyhow::Error::from)?;
+        Ok(Self {
+            partition: metadata.partition_id(),
+            offset: metadata.offset(),
+        })
+    }
+}
+
+impl TryIntoJs for ProduceMetadataJS {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        let mut metadata = JsObject::create(js_env)?;
+        metadata.set_property(PARTITION_KEY, self.partition.try_to_js(js_env)?)?;
+        metadata.set_property(OFFSET_KEY, BigInt::from(self.offset).try_to_js(js_env)?)?;
+        metadata.try_to_js(js_env)
+    }
+}
+
+/// Keys given as `null` or `undefined` produce records without a key
+fn record_key(key: Option<&ProduceArg>) -> RecordKey {
+    match key {
+        Some(key) => RecordKey::from(key.as_bytes()),
+        None => RecordKey::NULL,
+    }
+}
+
+/// Borrows the bytes of `elements`. JS buffers can't be shared between threads,
+/// so only the bytes are held while the records are queued.
+fn records(elements: &[(Nullable<ProduceArg>, ProduceArg)]) -> Vec<(RecordKey, &[u8])> {
+    elements
+        .iter()
+        .map(|(key, value)| (record_key(key.as_ref()), value.as_bytes()))
+        .collect()
+}
+
+/// Argument that JS may set to `null` or `undefined`. Unlike `Option`, which
+/// only covers arguments left out, it also works for elements of arrays.
+pub struct Nullable<T>(Option<T>);
+
+impl<T> Nullable<T> {
+    pub fn as_ref(&self) -> Option<&T> {
+        self.0.as_ref()
+    }
+
+    pub fn into_inner(self) -> Option<T> {
+        self.0
+    }
+}
+
+impl<'a, T: JSValue<'a>> JSValue<'a> for Nullable<T> {
+    fn convert_to_rust(env: &'a JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        if env.is_undefined_or_null(js_value)? {
+            return Ok(Self(None));
+        }
+        Ok(Self(Some(T::convert_to_rust(env, js_value)?)))
+    }
+}
+
+/// Callers may give 'string', 'ArrayBuffer' or 'ArrayBufferView' values to `producer.send`
+pub enum ProduceArg {
+    String(String),
+    ArrayBuffer(JSArrayBuffer),
+    View(ArrayBufferView),
+}
+
+impl ProduceArg {
+    pub fn as_bytes(&self) -> &[u8] {
+        match self {
+            Self::String(string) => string.as_bytes(),
+            Self::ArrayBuffer(buffer) => buffer.as_bytes(),
+            Self::View(view) => view.as_bytes(),
+        }
+    }
+}
+
+impl JSValue<'_> for ProduceArg {
+    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        // Try to convert value to string
+        if let Ok(string) = env.convert_to_rust::<String>(js_value) {
+            return Ok(Self::String(string));
+        }
+
+        // Try to convert value to ArrayBuffer
+        if let Ok(buffer) = env.convert_to_rust::<JSArrayBuffer>(js_value) {
+            return Ok(Self::ArrayBuffer(buffer));
+        }
+
+        // Try to convert value to a TypedArray, Buffer or DataView
+        if let Ok(view) = env.convert_to_rust::<ArrayBufferView>(js_value) {
+            return Ok(Self::View(view));
+        }
+
+        Err(NjError::Other(
+            "Producer args must be string, ArrayBuffer, TypedArray or DataView".to_string(),
+        ))
+    }
+}
+
+/// Bytes seen through a TypedArray, Node Buffer or DataView.
+/// The underlying ArrayBuffer is referenced rather than copied.
+pub struct ArrayBufferView {
+    buffer: JSArrayBuffer,
+    offset: usize,
+    length: usize,
+}
+
+impl ArrayBufferView {
+    fn as_bytes(&self) -> &[u8] {
+        &self.buffer.as_bytes()[self.offset..self.offset + self.length]
+    }
+}
+
+impl JSValue<'_> for ArrayBufferView {
+    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        let mut arraybuffer = ptr::null_mut();
+        let mut offset = 0;
+        let mut length = 0;
+
+        let mut is_typedarray = false;
+        napi_call_result!(sys::napi_is_typedarray(
+            env.inner(),
+            js_value,
+            &mut is_typedarray
+        ))?;
+        let mut is_dataview = false;
+        napi_call_result!(sys::napi_is_dataview(
+            env.inner(),
+            js_value,
+            &mut is_dataview
+        ))?;
+
+        if is_typedarray {
+            let mut array_type = 0;
+            let mut elements = 0;
+            napi_call_result!(sys::napi_get_typedarray_info(
+                env.inner(),
+                js_value,
+                &mut array_type,
+                &mut elements,
+                ptr::null_mut(),
+                &mut arraybuffer,
+                &mut offset
+            ))?;
+            length = elements
+                .checked_mul(typedarray_element_size(array_type)?)
+                .ok_or_else(|| NjError::Other("TypedArray is too large".to_string()))?;
+        } else if is_dataview {
+            napi_call_result!(sys::napi_get_dataview_info(
+                env.inner(),
+                js_value,
+                &mut length,
+                ptr::null_mut(),
+                &mut arraybuffer,
+                &mut offset
+            ))?;
+        } else {
+            return Err(NjError::Other(
+                "value is not a TypedArray or DataView".to_string(),
+            ));
+        }
+
+        let buffer = env.convert_to_rust::<JSArrayBuffer>(arraybuffer)?;
+        // A detached buffer no longer holds the bytes of its views
+        match offset.checked_add(length) {
+            Some(end) if end <= buffer.as_bytes().len() => Ok(Self {
+                buffer,
+                offset,
+                length,
+            }),
+            _ => Err(NjError::Other(format!(
+                "view of {} bytes at offset {} is out of its buffer bounds",
+                length, offset
+            ))),
+        }
+    }
+}
+
+/// Bytes per element of a TypedArray
+fn typedarray_element_size(array_type: sys::napi_typedarray_type) -> Result<usize, NjError> {
+    match array_type {
+        sys::napi_typedarray_type_napi_int8_array
+        | sys::napi_typedarray_type_napi_uint8_array
+        | sys::napi_typedarray_type_napi_uint8_clamped_array => Ok(1),
+        sys::napi_typedarray_type_napi_int16_array
+        | sys::napi_typedarray_type_napi_uint16_array => Ok(2),
+        sys::napi_typedarray_type_napi_int32_array
+        | sys::napi_typedarray_type_napi_uint32_array
+        | sys::napi_typedarray_type_napi_float32_array => Ok(4),
+        sys::napi_typedarray_type_napi_float64_array
+        | sys::napi_typedarray_type_napi_bigint64_array
+        | sys::napi_typedarray_type_napi_biguint64_array => Ok(8),
+        _ => Err(NjError::Other(format!(
+            "unsupported TypedArray type {}",
+            array_type
+        ))),
+    }
+}
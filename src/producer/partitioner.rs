@@ -0,0 +1,126 @@
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use siphasher::sip::SipHasher;
+
+use fluvio::{Partitioner, PartitionerConfig};
+
+/// Number of consecutive keyless records the sticky partitioner sends to one partition
+const STICKY_RECORDS: u32 = 100;
+
+/// Partitioners that can be chosen by name in the producer config
+#[derive(Debug, Clone, Copy)]
+pub enum PartitionerKind {
+    RoundRobin,
+    KeyHash,
+    Sticky,
+}
+
+impl PartitionerKind {
+    pub const ROUND_ROBIN: &'static str = "round-robin";
+    pub const KEY_HASH: &'static str = "key-hash";
+    pub const STICKY: &'static str = "sticky";
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            Self::ROUND_ROBIN => Some(Self::RoundRobin),
+            Self::KEY_HASH => Some(Self::KeyHash),
+            Self::STICKY => Some(Self::Sticky),
+            _ => None,
+        }
+    }
+
+    pub fn build(self) -> Box<dyn Partitioner + Send + Sync> {
+        match self {
+            Self::RoundRobin => Box::<RoundRobinPartitioner>::default(),
+            Self::KeyHash => Box::<KeyHashPartitioner>::default(),
+            Self::Sticky => Box::<StickyPartitioner>::default(),
+        }
+    }
+}
+
+/// Partition that the records of the send in progress are routed to, if any
+pub type TargetPartition = Arc<Mutex<Option<u32>>>;
+
+/// Sends records to the target partition when one is set, and otherwise
+/// lets the configured partitioner, or Fluvio's default one, pick it
+pub struct TargetPartitioner {
+    target: TargetPartition,
+    inner: Box<dyn Partitioner + Send + Sync>,
+}
+
+impl TargetPartitioner {
+    pub fn new(target: TargetPartition, kind: Option<PartitionerKind>) -> Self {
+        let inner = match kind {
+            Some(kind) => kind.build(),
+            None => Box::<KeyHashPartitioner>::default(),
+        };
+        Self { target, inner }
+    }
+}
+
+impl Partitioner for TargetPartitioner {
+    fn partition(&self, config: &PartitionerConfig, key: Option<&[u8]>, value: &[u8]) -> u32 {
+        let target = *self.target.lock().unwrap();
+        match target {
+            Some(partition) => partition,
+            None => self.inner.partition(config, key, value),
+        }
+    }
+}
+
+/// Sends each record to the next partition, ignoring keys
+#[derive(Default)]
+struct RoundRobinPartitioner {
+    next: AtomicU32,
+}
+
+impl Partitioner for RoundRobinPartitioner {
+    fn partition(&self, config: &PartitionerConfig, _key: Option<&[u8]>, _value: &[u8]) -> u32 {
+        self.next.fetch_add(1, Ordering::Relaxed) % config.partition_count().max(1)
+    }
+}
+
+/// Hashes keys with SipHash and sends keyless records round-robin, like
+/// Fluvio's default partitioner, which the client doesn't expose
+#[derive(Default)]
+struct KeyHashPartitioner {
+    keyless: RoundRobinPartitioner,
+}
+
+impl Partitioner for KeyHashPartitioner {
+    fn partition(&self, config: &PartitionerConfig, key: Option<&[u8]>, value: &[u8]) -> u32 {
+        match key {
+            Some(key) => hash_partition(key, config.partition_count()),
+            None => self.keyless.partition(config, key, value),
+        }
+    }
+}
+
+/// Partition of `key` as Fluvio computes it
+fn hash_partition(key: &[u8], partition_count: u32) -> u32 {
+    let mut hasher = SipHasher::new();
+    key.hash(&mut hasher);
+    (hasher.finish() % u64::from(partition_count.max(1))) as u32
+}
+
+/// Hashes keys like Fluvio's default partitioner, but sends runs of keyless
+/// records to the same partition so they fill batches before moving on
+#[derive(Default)]
+struct StickyPartitioner {
+    keyed: KeyHashPartitioner,
+    keyless: AtomicU32,
+}
+
+impl Partitioner for StickyPartitioner {
+    fn partition(&self, config: &PartitionerConfig, key: Option<&[u8]>, value: &[u8]) -> u32 {
+        match key {
+            Some(_) => self.keyed.partition(config, key, value),
+            None => {
+                let run = self.keyless.fetch_add(1, Ordering::Relaxed) / STICKY_RECORDS;
+                run % config.partition_count().max(1)
+            }
+        }
+    }
+}
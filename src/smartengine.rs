@@ -0,0 +1,178 @@
+use std::thread;
+
+use tracing::debug;
+use anyhow::{anyhow, Result};
+use futures_channel::oneshot;
+
+use fluvio::SmartModuleInvocation;
+use fluvio::dataplane::record::Record;
+
+use node_bindgen::derive::node_bindgen;
+use node_bindgen::core::NjError;
+use node_bindgen::core::JSValue;
+use node_bindgen::core::TryIntoJs;
+use node_bindgen::core::val::{JsEnv, JsObject};
+use node_bindgen::core::buffer::ArrayBuffer;
+use node_bindgen::sys::napi_value;
+
+use crate::error::FluvioErrorJS;
+use crate::producer::{Nullable, ProduceArg};
+use crate::smartmodule::smartmodule_invocations;
+
+const KEY_KEY: &str = "key";
+const VALUE_KEY: &str = "value";
+#[cfg(not(feature = "smartengine"))]
+const ENGINE_DISABLED_MSG: &str =
+    "testSmartModule requires the native module to be built with the `smartengine` feature";
+
+#[cfg(feature = "smartengine")]
+use self::engine::run_chain;
+
+/// Runs the SmartModules of `config` over `records` in this process, without a cluster.
+/// Resolves with the records output by the last SmartModule of the chain.
+#[node_bindgen]
+async fn test_smartmodule(
+    config: SmartModuleTestConfig,
+    records: Vec<(Nullable<ProduceArg>, ProduceArg)>,
+) -> Result<Vec<TestRecordJS>, FluvioErrorJS> {
+    let records = records
+        .iter()
+        .map(|(key, value)| match key.as_ref() {
+            Some(key) => Record::new_key_value(key.as_bytes().to_vec(), value.as_bytes().to_vec()),
+            None => Record::new(value.as_bytes().to_vec()),
+        })
+        .collect();
+
+    // Running WASM blocks, so the chain gets its own thread
+    let (sender, receiver) = oneshot::channel();
+    thread::spawn(move || {
+        let _ = sender.send(run_chain(config.0, records));
+    });
+    let output = receiver
+        .await
+        .map_err(|_| anyhow!("SmartModule chain stopped before returning its output"))
+        .and_then(|output| output)
+        .map_err(FluvioErrorJS::smartmodule)?;
+    debug!("SmartModule chain output {} records", output.len());
+    Ok(output.into_iter().map(TestRecordJS::from).collect())
+}
+
+/// SmartModules read from the options shared with consumer and producer configs
+struct SmartModuleTestConfig(Vec<SmartModuleInvocation>);
+
+impl JSValue<'_> for SmartModuleTestConfig {
+    fn convert_to_rust(env: &JsEnv, js_value: napi_value) -> Result<Self, NjError> {
+        let js_obj = env.convert_to_rust::<JsObject>(js_value)?;
+        let invocations = smartmodule_invocations(&js_obj)?;
+        if invocations.is_empty() {
+            return Err(NjError::Other(
+                "at least one SmartModule must be given to test".to_owned(),
+            ));
+        }
+        Ok(Self(invocations))
+    }
+}
+
+#[cfg(not(feature = "smartengine"))]
+fn run_chain(
+    _invocations: Vec<SmartModuleInvocation>,
+    _records: Vec<Record>,
+) -> Result<Vec<Record>> {
+    Err(anyhow!(ENGINE_DISABLED_MSG))
+}
+
+/// Only built with the `smartengine` feature, as it pulls in the WASM runtime
+#[cfg(feature = "smartengine")]
+mod engine {
+    use std::io::Read;
+
+    use anyhow::{anyhow, Context, Result};
+    use flate2::read::GzDecoder;
+
+    use fluvio::{SmartModuleInvocation, SmartModuleInvocationWasm, SmartModuleKind};
+    use fluvio::dataplane::record::Record;
+    use fluvio_smartmodule::dataplane::smartmodule::SmartModuleInput;
+    use fluvio_smartengine::{
+        SmartEngine, SmartModuleChainBuilder, SmartModuleConfig, SmartModuleInitialData,
+        DEFAULT_SMARTENGINE_VERSION,
+    };
+    use fluvio_smartengine::metrics::SmartModuleChainMetrics;
+
+    /// Lookbacks are not run, as there is no topic to read back from
+    pub fn run_chain(
+        invocations: Vec<SmartModuleInvocation>,
+        records: Vec<Record>,
+    ) -> Result<Vec<Record>> {
+        let engine = SmartEngine::new();
+        let mut chain_builder = SmartModuleChainBuilder::default();
+
+        for invocation in invocations {
+            let wasm = match invocation.wasm {
+                SmartModuleInvocationWasm::AdHoc(gzipped) => gunzip(&gzipped)?,
+                SmartModuleInvocationWasm::Predefined(name) => {
+                    return Err(anyhow!(
+                        "SmartModule \"{}\" is stored on the cluster, give its file or data to test it locally",
+                        name
+                    ))
+                }
+            };
+            let initial_data = match invocation.kind {
+                SmartModuleKind::Aggregate { accumulator } => {
+                    SmartModuleInitialData::with_aggregate(accumulator)
+                }
+                _ => SmartModuleInitialData::None,
+            };
+            let config = SmartModuleConfig::builder()
+                .params(invocation.params)
+                .initial_data(initial_data)
+                .build()?;
+            chain_builder.add_smart_module(config, wasm);
+        }
+
+        let mut chain = chain_builder
+            .initialize(&engine)
+            .context("Failed to initialize SmartModule chain")?;
+        let input = SmartModuleInput::try_from_records(records, DEFAULT_SMARTENGINE_VERSION)?;
+        let output = chain.process(input, &SmartModuleChainMetrics::default())?;
+
+        if let Some(error) = output.error {
+            return Err(error.into());
+        }
+        Ok(output.successes)
+    }
+
+    /// SmartModule payloads are always Gzipped once read from a config
+    fn gunzip(gzipped: &[u8]) -> Result<Vec<u8>> {
+        let mut wasm = Vec::new();
+        GzDecoder::new(gzipped)
+            .read_to_end(&mut wasm)
+            .context("Failed to decompress SmartModule")?;
+        Ok(wasm)
+    }
+}
+
+/// Record output by `test_smartmodule`
+pub struct TestRecordJS {
+    key: Option<Vec<u8>>,
+    value: Vec<u8>,
+}
+
+impl From<Record> for TestRecordJS {
+    fn from(record: Record) -> Self {
+        Self {
+            key: record.key().map(|key| key.as_ref().to_vec()),
+            value: record.value().as_ref().to_vec(),
+        }
+    }
+}
+
+impl TryIntoJs for TestRecordJS {
+    fn try_to_js(self, js_env: &JsEnv) -> Result<napi_value, NjError> {
+        let mut record = JsObject::create(js_env)?;
+        if let Some(key) = self.key {
+            record.set_property(KEY_KEY, ArrayBuffer::new(key).try_to_js(js_env)?)?;
+        }
+        record.set_property(VALUE_KEY, ArrayBuffer::new(self.value).try_to_js(js_env)?)?;
+        record.try_to_js(js_env)
+    }
+}
@@ -0,0 +1,283 @@
+use std::collections::BTreeMap;
+use std::io::prelude::*;
+use std::io::BufReader;
+use std::fs::File;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use tracing::debug;
+use base64::Engine;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+
+use fluvio::{
+    SmartModuleInvocation, SmartModuleKind, SmartModuleInvocationWasm, SmartModuleExtraParams,
+};
+use fluvio_smartmodule::dataplane::smartmodule::Lookback;
+
+use node_bindgen::core::NjError;
+use node_bindgen::core::val::JsObject;
+
+use crate::{optional_property, must_property};
+use crate::producer::ProduceArg;
+
+const CONFIG_SMART_MODULES_KEY: &str = "smartmodules";
+
+/// First bytes of a Gzip stream
+const GZIP_MAGIC: &[u8] = &[0x1f, 0x8b];
+/// First bytes of a WASM binary module, `\0asm`
+const WASM_MAGIC: &[u8] = &[0x00, 0x61, 0x73, 0x6d];
+
+const LOOKBACK_LAST_KEY: &str = "last";
+const LOOKBACK_AGE_KEY: &str = "ageMs";
+
+/// Property names describing a single SmartModule
+struct SmartModuleKeys {
+    kind: &'static str,
+    file: &'static str,
+    name: &'static str,
+    data: &'static str,
+    params: &'static str,
+    accumulator: &'static str,
+    lookback: &'static str,
+}
+
+/// Keys of the single SmartModule given directly in a config
+const CONFIG_KEYS: SmartModuleKeys = SmartModuleKeys {
+    kind: "smartmoduleType",
+    file: "smartmoduleFile",
+    name: "smartmoduleName",
+    data: "smartmoduleData",
+    params: "smartmoduleParams",
+    accumulator: "smartmoduleAccumulator",
+    lookback: "smartmoduleLookback",
+};
+
+/// Keys of each entry in the `smartmodules` chain
+const CHAIN_KEYS: SmartModuleKeys = SmartModuleKeys {
+    kind: "type",
+    file: "file",
+    name: "name",
+    data: "data",
+    params: "params",
+    accumulator: "accumulator",
+    lookback: "lookback",
+};
+
+/// Reads the SmartModule options shared by consumer and producer configs.
+/// Either a single SmartModule is given with `smartmoduleType` and one of
+/// `smartmoduleFile`, `smartmoduleName` or `smartmoduleData`, or an ordered
+/// chain is given in `smartmodules`.
+pub fn smartmodule_invocations(js_obj: &JsObject) -> Result<Vec<SmartModuleInvocation>, NjError> {
+    let single = smartmodule_invocation(js_obj, &CONFIG_KEYS)?;
+    let chain = optional_property!(CONFIG_SMART_MODULES_KEY, Vec<JsObject>, js_obj);
+
+    match (single, chain) {
+        (None, None) => Ok(vec![]),
+        (Some(invocation), None) => Ok(vec![invocation]),
+        (None, Some(chain)) => chain
+            .iter()
+            .map(|entry| {
+                smartmodule_invocation(entry, &CHAIN_KEYS)?.ok_or_else(|| {
+                    NjError::Other(format!(
+                        "Each entry of {} must provide one of {}, {} or {}",
+                        CONFIG_SMART_MODULES_KEY, CHAIN_KEYS.file, CHAIN_KEYS.name, CHAIN_KEYS.data
+                    ))
+                })
+            })
+            .collect(),
+        (Some(_), Some(_)) => Err(NjError::Other(format!(
+            "{} can not be combined with {}, {} or {}",
+            CONFIG_SMART_MODULES_KEY, CONFIG_KEYS.file, CONFIG_KEYS.name, CONFIG_KEYS.data
+        ))),
+    }
+}
+
+/// Whether a property is set to something other than `undefined` or `null`
+fn is_defined(js_obj: &JsObject, key: &str) -> Result<bool, NjError> {
+    match js_obj.get_property(key)? {
+        Some(prop) => Ok(!prop.env().is_undefined_or_null(prop.napi_value())?),
+        None => Ok(false),
+    }
+}
+
+/// Reads one SmartModule, or `None` if no source is given.
+/// The kind is only required when a source is given.
+fn smartmodule_invocation(
+    js_obj: &JsObject,
+    keys: &SmartModuleKeys,
+) -> Result<Option<SmartModuleInvocation>, NjError> {
+    let smartmodule_file = optional_property!(keys.file, String, js_obj);
+    let smartmodule_name = optional_property!(keys.name, String, js_obj);
+    // A string is Base64 encoded, any other value holds the module bytes
+    let smartmodule_data = optional_property!(keys.data, ProduceArg, js_obj);
+
+    let wasm = match (smartmodule_file, smartmodule_name, smartmodule_data) {
+        (None, None, None) => {
+            // Options of a SmartModule are an error without the SmartModule itself
+            for key in [keys.params, keys.accumulator, keys.lookback] {
+                if is_defined(js_obj, key)? {
+                    return Err(NjError::Other(format!(
+                        "{} requires one of {}, {} or {}",
+                        key, keys.file, keys.name, keys.data
+                    )));
+                }
+            }
+            return Ok(None);
+        }
+        (Some(file_path), None, None) => {
+            SmartModuleInvocationWasm::AdHoc(read_wasm_file(&file_path)?)
+        }
+        (None, Some(name), None) => SmartModuleInvocationWasm::Predefined(name),
+        (None, None, Some(data)) => SmartModuleInvocationWasm::AdHoc(read_wasm_data(data)?),
+        _ => {
+            return Err(NjError::Other(format!(
+                "You must either provide one of {}, {} or {}",
+                keys.file, keys.name, keys.data
+            )))
+        }
+    };
+
+    let smartmodule_type = must_property!(keys.kind, String, js_obj);
+    let accumulator = optional_property!(keys.accumulator, ProduceArg, js_obj);
+    let kind = match (smartmodule_type.as_str(), accumulator) {
+        // Aggregates start from an empty accumulator unless one is given
+        ("aggregate", accumulator) => Ok(SmartModuleKind::Aggregate {
+            accumulator: accumulator
+                .map(|accumulator| accumulator.as_bytes().to_vec())
+                .unwrap_or_default(),
+        }),
+        (_, Some(_)) => Err(NjError::Other(format!(
+            "{} can only be used with the \"aggregate\" SmartModule type",
+            keys.accumulator
+        ))),
+        ("filter", None) => Ok(SmartModuleKind::Filter),
+        ("map", None) => Ok(SmartModuleKind::Map),
+        ("array_map", None) => Ok(SmartModuleKind::ArrayMap),
+        ("filter_map", None) => Ok(SmartModuleKind::FilterMap),
+        _ => Err(NjError::Other(format!(
+            "Provided SmartModule type: \"{}\" is not valid",
+            smartmodule_type
+        ))),
+    }?;
+
+    // `index.ts` passes the params map as its `[key, value]` entries
+    let params: BTreeMap<String, String> =
+        optional_property!(keys.params, Vec<(String, String)>, js_obj)
+            .unwrap_or_default()
+            .into_iter()
+            .collect();
+    let lookback = match optional_property!(keys.lookback, JsObject, js_obj) {
+        Some(lookback_obj) => Some(lookback(&lookback_obj, keys)?),
+        None => None,
+    };
+
+    Ok(Some(SmartModuleInvocation {
+        wasm,
+        kind,
+        params: SmartModuleExtraParams::new(params, lookback),
+    }))
+}
+
+/// Reads records the SmartModule looks back at before processing the stream,
+/// the last `last` records, those newer than `ageMs`, or both
+fn lookback(js_obj: &JsObject, keys: &SmartModuleKeys) -> Result<Lookback, NjError> {
+    let last = optional_property!(LOOKBACK_LAST_KEY, f64, js_obj);
+    let age = optional_property!(LOOKBACK_AGE_KEY, f64, js_obj);
+
+    // NaN would otherwise be read as 0
+    if [last, age]
+        .iter()
+        .flatten()
+        .any(|value| !value.is_finite() || *value < 0.0)
+    {
+        return Err(NjError::Other(format!(
+            "{}.{} and {}.{} must be non-negative numbers",
+            keys.lookback, LOOKBACK_LAST_KEY, keys.lookback, LOOKBACK_AGE_KEY
+        )));
+    }
+
+    match (last, age) {
+        (Some(last), None) => Ok(Lookback::last(last as u64)),
+        // A `last` of 0 looks back at every record within `age`
+        (last, Some(age)) => Ok(Lookback::age(
+            Duration::from_millis(age as u64),
+            last.map(|last| last as u64),
+        )),
+        (None, None) => Err(NjError::Other(format!(
+            "{} must provide {}, {} or both",
+            keys.lookback, LOOKBACK_LAST_KEY, LOOKBACK_AGE_KEY
+        ))),
+    }
+}
+
+/// Reads a WASM module given as a Base64 encoded string or as bytes, plain or Gzipped.
+/// The module is returned Gzipped, as by `read_wasm_file`.
+pub fn read_wasm_data(data: ProduceArg) -> Result<Vec<u8>, NjError> {
+    match data {
+        ProduceArg::String(data) => gzip_wasm(decode_wasm_data(&data)?),
+        data => gzip_wasm(data.as_bytes().to_vec()),
+    }
+}
+
+/// Reads a WASM file, plain or Gzipped, returning the Gzipped module
+pub fn read_wasm_file(file_path: &str) -> Result<Vec<u8>, NjError> {
+    debug!("Loads SmartModule file from {}", file_path);
+    let path = PathBuf::from_str(file_path).map_err(|e| NjError::Other(e.to_string()))?;
+    let file = File::open(path).map_err(|io_err| {
+        NjError::Other(format!(
+            "An error ocurred opening file on {}. {:?}",
+            file_path, io_err,
+        ))
+    })?;
+    let mut reader = BufReader::new(file);
+    let mut buff: Vec<u8> = Vec::new();
+
+    reader.read_to_end(&mut buff).map_err(|io_err| {
+        NjError::Other(format!(
+            "Failed to read contents of file on {}: {:?}",
+            file_path, io_err
+        ))
+    })?;
+    gzip_wasm(buff)
+}
+
+/// Gzips a WASM module, as expected by the SPU.
+/// Modules already Gzipped are detected from their magic bytes and kept as is.
+fn gzip_wasm(wasm: Vec<u8>) -> Result<Vec<u8>, NjError> {
+    if wasm.starts_with(GZIP_MAGIC) {
+        return Ok(wasm);
+    }
+    if !wasm.starts_with(WASM_MAGIC) {
+        return Err(NjError::Other(
+            "SmartModule is neither a WASM module nor a Gzipped WASM module".to_owned(),
+        ));
+    }
+
+    let mut gz_encoder = GzEncoder::new(Vec::new(), Compression::default());
+    gz_encoder.write_all(wasm.as_slice()).map_err(|io_err| {
+        NjError::Other(format!(
+            "An error ocurred while reading WASM module: {:?}",
+            io_err
+        ))
+    })?;
+    gz_encoder.finish().map_err(|io_err| {
+        NjError::Other(format!(
+            "An error ocurred while encoding WASM into Gzip. {:?}",
+            io_err
+        ))
+    })
+}
+
+/// Decodes a Base64 encoded WASM module
+fn decode_wasm_data(data: &str) -> Result<Vec<u8>, NjError> {
+    base64::engine::general_purpose::STANDARD
+        .decode(data)
+        .map_err(|e| {
+            NjError::Other(format!(
+                "An error ocurred attempting to decode the Base64 WASM file provided. {:?}",
+                e
+            ))
+        })
+}